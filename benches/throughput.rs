@@ -0,0 +1,106 @@
+use anyhow::{bail, Result};
+use streams_examples::{
+    bench::{self, BenchConfig, CHANNEL_TYPES},
+    examples::SeedProvider,
+    transports,
+};
+
+const USAGE: &str = "Usage:
+    cargo bench --bench throughput -- [--channel-types TYPE,...] [--messages N,...]
+                                      [--subscribers N,...] [--keyloads N] [--rounds N]
+                                      [--seed SEED] [--json FILE]
+
+Measures send_keyload, send_signed_packet and fetch_all_next_msgs against the in-memory transport for
+every combination of channel type, message count and subscriber count, reporting ops/sec and latency
+percentiles. Channel types are single_branch, multi_branch and single_depth.";
+
+struct Options {
+    channel_types: Vec<String>,
+    messages: Vec<usize>,
+    subscribers: Vec<usize>,
+    keyloads: usize,
+    rounds: usize,
+    seed: u64,
+    json: Option<String>,
+}
+
+fn parse_list<T: std::str::FromStr>(value: Option<String>, flag: &str) -> Result<Vec<T>>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    match value {
+        Some(value) => Ok(value.split(',').map(str::parse).collect::<Result<_, _>>()?),
+        None => bail!("Missing value for {}", flag),
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options> {
+    let mut options = Options {
+        channel_types: CHANNEL_TYPES.iter().map(|t| t.to_string()).collect(),
+        messages: vec![10, 100],
+        subscribers: vec![1, 5],
+        keyloads: 3,
+        rounds: 3,
+        seed: 0,
+        json: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--channel-types" => options.channel_types = parse_list(args.next(), &arg)?,
+            "--messages" => options.messages = parse_list(args.next(), &arg)?,
+            "--subscribers" => options.subscribers = parse_list(args.next(), &arg)?,
+            "--keyloads" => options.keyloads = single(parse_list(args.next(), &arg)?, &arg)?,
+            "--rounds" => options.rounds = single(parse_list(args.next(), &arg)?, &arg)?,
+            "--seed" => options.seed = single(parse_list(args.next(), &arg)?, &arg)?,
+            "--json" => match args.next() {
+                Some(path) => options.json = Some(path),
+                None => bail!("Missing value for --json"),
+            },
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            // Passed along by cargo bench
+            "--bench" => {}
+            flag => bail!("Unknown option: {}\n\n{}", flag, USAGE),
+        }
+    }
+    Ok(options)
+}
+
+fn single<T>(mut values: Vec<T>, flag: &str) -> Result<T> {
+    match values.len() {
+        1 => Ok(values.remove(0)),
+        _ => bail!("Expected a single value for {}", flag),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let options = parse_args(std::env::args().skip(1))?;
+    let mut seeds = SeedProvider::deterministic(options.seed);
+
+    let mut results = Vec::new();
+    for channel_type in &options.channel_types {
+        for messages in &options.messages {
+            for subscribers in &options.subscribers {
+                let config = BenchConfig {
+                    channel_type: bench::parse_channel_type(channel_type)?,
+                    messages: *messages,
+                    subscribers: *subscribers,
+                    keyloads: options.keyloads,
+                    rounds: options.rounds,
+                };
+                let result = bench::run(config, transports::offline, &mut seeds).await?;
+                println!("{}", result);
+                results.push(result);
+            }
+        }
+    }
+
+    if let Some(path) = &options.json {
+        std::fs::write(path, serde_json::to_string_pretty(&results)?)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use crate::examples::{report::channel_type_name, SeedProvider};
+use anyhow::{bail, ensure};
+use iota_streams::{
+    app_channels::api::tangle::{Author, Bytes, ChannelType, MessageContent, Subscriber, Transport},
+    core::Result,
+};
+use serde::Serialize;
+use std::{fmt, time::Instant};
+
+pub mod stats;
+pub use stats::{OpStats, Samples};
+
+/// Names of the channel types that can be benchmarked
+pub const CHANNEL_TYPES: [&str; 3] = ["single_branch", "multi_branch", "single_depth"];
+
+pub fn parse_channel_type(name: &str) -> Result<ChannelType> {
+    match name {
+        "single_branch" => Ok(ChannelType::SingleBranch),
+        "multi_branch" => Ok(ChannelType::MultiBranch),
+        "single_depth" => Ok(ChannelType::SingleDepth),
+        _ => bail!("Unknown channel type: {}", name),
+    }
+}
+
+/// Shape of a benchmarked channel. Each round creates a fresh channel with `subscribers` subscribers,
+/// sends `keyloads` keyloads for all of them and `messages` signed packets, after which every
+/// subscriber fetches the channel
+pub struct BenchConfig {
+    pub channel_type: ChannelType,
+    pub messages: usize,
+    pub subscribers: usize,
+    pub keyloads: usize,
+    pub rounds: usize,
+}
+
+/// Throughput and latency of each operation of a benchmarked channel shape
+#[derive(Clone, Debug, Serialize)]
+pub struct BenchResult {
+    pub channel_type: String,
+    pub messages: usize,
+    pub subscribers: usize,
+    pub keyloads: usize,
+    pub rounds: usize,
+    pub ops: Vec<OpStats>,
+}
+
+impl fmt::Display for BenchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} - {} msgs, {} subscribers, {} keyloads, {} rounds",
+            self.channel_type, self.messages, self.subscribers, self.keyloads, self.rounds
+        )?;
+        for op in &self.ops {
+            writeln!(f, "\t{}", op)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct OpSamples {
+    keyload: Samples,
+    publish: Samples,
+    fetch: Samples,
+}
+
+/// Benchmark the provided channel shape, creating a new transport for every round
+pub async fn run<T: Transport, F: Fn() -> T>(
+    config: BenchConfig,
+    new_transport: F,
+    seeds: &mut SeedProvider,
+) -> Result<BenchResult> {
+    let mut samples = OpSamples::default();
+    for _ in 0..config.rounds {
+        round(&config, new_transport(), seeds, &mut samples).await?;
+    }
+
+    Ok(BenchResult {
+        channel_type: channel_type_name(&config.channel_type).to_string(),
+        messages: config.messages,
+        subscribers: config.subscribers,
+        keyloads: config.keyloads,
+        rounds: config.rounds,
+        ops: vec![
+            samples.keyload.stats("send_keyload"),
+            samples.publish.stats("send_signed_packet"),
+            samples.fetch.stats("fetch_all_next_msgs"),
+        ],
+    })
+}
+
+async fn round<T: Transport>(
+    config: &BenchConfig,
+    transport: T,
+    seeds: &mut SeedProvider,
+    samples: &mut OpSamples,
+) -> Result<()> {
+    let single_depth = matches!(config.channel_type, ChannelType::SingleDepth);
+    let seed: &str = &seeds.author_seed();
+    let mut author = Author::new(seed, copy_channel_type(&config.channel_type), transport.clone());
+    let announcement_link = author.send_announce().await?;
+
+    let mut subscribers = Vec::with_capacity(config.subscribers);
+    for i in 0..config.subscribers {
+        let mut subscriber = Subscriber::new(&format!("Subscriber{}", i), transport.clone());
+        subscriber.receive_announcement(&announcement_link).await?;
+        let subscribe_link = subscriber.send_subscribe(&announcement_link).await?;
+        author.receive_subscribe(&subscribe_link).await?;
+        subscribers.push(subscriber);
+    }
+
+    // Keyloads are chained, except in single depth channels where everything hangs off the anchor
+    let mut prev_msg_link = announcement_link.clone();
+    for _ in 0..config.keyloads {
+        let link_to = if single_depth { &announcement_link } else { &prev_msg_link };
+        let start = Instant::now();
+        let (keyload_link, _seq_link) = author.send_keyload_for_everyone(link_to).await?;
+        samples.keyload.record(start, 1);
+        prev_msg_link = keyload_link;
+    }
+
+    let anchor_link = prev_msg_link.clone();
+    for i in 0..config.messages {
+        let link_to = if single_depth { &anchor_link } else { &prev_msg_link };
+        let payload = Bytes(format!("Benchmark message {}", i).into_bytes());
+        let start = Instant::now();
+        let (msg_link, _seq_link) = author.send_signed_packet(link_to, &Bytes::default(), &payload).await?;
+        samples.publish.record(start, 1);
+        prev_msg_link = msg_link;
+    }
+
+    for subscriber in subscribers.iter_mut() {
+        let start = Instant::now();
+        let retrieved = subscriber.fetch_all_next_msgs().await;
+        samples.fetch.record(start, retrieved.len());
+
+        let packets = retrieved
+            .iter()
+            .filter(|msg| matches!(msg.body, MessageContent::SignedPacket { .. }))
+            .count();
+        ensure!(
+            packets == config.messages,
+            "Subscriber retrieved {} of {} messages",
+            packets,
+            config.messages
+        );
+    }
+
+    Ok(())
+}
+
+// A fresh `ChannelType` is needed for every round's Author
+fn copy_channel_type(channel_type: &ChannelType) -> ChannelType {
+    match channel_type {
+        ChannelType::SingleBranch => ChannelType::SingleBranch,
+        ChannelType::MultiBranch => ChannelType::MultiBranch,
+        ChannelType::SingleDepth => ChannelType::SingleDepth,
+    }
+}
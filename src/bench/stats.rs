@@ -0,0 +1,97 @@
+use serde::Serialize;
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Latencies recorded for one operation, along with the number of items (messages) each call handled
+#[derive(Clone, Debug, Default)]
+pub struct Samples {
+    latencies: Vec<Duration>,
+    items: usize,
+}
+
+impl Samples {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a call that started at `start` and handled `items` messages
+    pub fn record(&mut self, start: Instant, items: usize) {
+        self.latencies.push(start.elapsed());
+        self.items += items;
+    }
+
+    pub fn len(&self) -> usize {
+        self.latencies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.latencies.is_empty()
+    }
+
+    /// Latency below which `percentile` percent of the calls completed (nearest rank)
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        if self.latencies.is_empty() {
+            return Duration::default();
+        }
+        let mut sorted = self.latencies.clone();
+        sorted.sort();
+        let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+
+    pub fn stats(&self, op: &str) -> OpStats {
+        let total: Duration = self.latencies.iter().sum();
+        let per_sec = |count: usize| match total.as_secs_f64() {
+            secs if secs > 0.0 => count as f64 / secs,
+            _ => 0.0,
+        };
+        OpStats {
+            op: op.to_string(),
+            calls: self.len(),
+            items: self.items,
+            ops_per_sec: per_sec(self.len()),
+            items_per_sec: per_sec(self.items),
+            p50_ms: millis(self.percentile(50.0)),
+            p90_ms: millis(self.percentile(90.0)),
+            p99_ms: millis(self.percentile(99.0)),
+            max_ms: millis(self.percentile(100.0)),
+        }
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Throughput and latency percentiles of one operation
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OpStats {
+    pub op: String,
+    pub calls: usize,
+    pub items: usize,
+    pub ops_per_sec: f64,
+    pub items_per_sec: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl fmt::Display for OpStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<20} {:>6} calls {:>10.1} ops/s {:>10.1} msgs/s   p50 {:>8.3}ms  p90 {:>8.3}ms  p99 {:>8.3}ms  max {:>8.3}ms",
+            self.op,
+            self.calls,
+            self.ops_per_sec,
+            self.items_per_sec,
+            self.p50_ms,
+            self.p90_ms,
+            self.p99_ms,
+            self.max_ms
+        )
+    }
+}
@@ -0,0 +1,33 @@
+use anyhow::{bail, Result};
+use std::net::{Ipv4Addr, SocketAddr};
+use streams_examples::local_node::{LocalNode, Store, DEFAULT_PORT};
+
+const USAGE: &str = "Usage:
+    local_node [--bind ADDRESS] [--store FILE]
+
+Serves the subset of the node HTTP API used by Streams on 127.0.0.1:14265 by default. Messages are
+kept in memory unless a store file is provided.";
+
+fn main() -> Result<()> {
+    let mut addr = SocketAddr::from((Ipv4Addr::LOCALHOST, DEFAULT_PORT));
+    let mut store = Store::in_memory();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--bind", Some(value)) => addr = value.parse()?,
+            ("--store", Some(path)) => store = Store::open(path)?,
+            ("-h", _) | ("--help", _) => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => bail!("Unexpected argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    println!("Loaded {} stored messages", store.len());
+    let node = LocalNode::bind(addr, store)?;
+    println!("Local node listening on {}", node.url()?);
+    node.serve();
+    Ok(())
+}
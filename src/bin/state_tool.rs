@@ -0,0 +1,110 @@
+use anyhow::{bail, Result};
+use std::path::PathBuf;
+use streams_examples::{
+    state::{self, secret::PASSWORD_ENV_VAR, PasswordSource, Role},
+    transports,
+};
+
+const USAGE: &str = "Usage:
+    state_tool inspect FILE [--role author|subscriber] [--password-file FILE] [--json]
+    state_tool diff FIRST SECOND [--role author|subscriber] [--password-file FILE] [--json]
+
+Imports an exported state and prints its announcement link, channel type, known publishers with
+their sequence states, stored PSK ids and subscriber public keys. States are imported as an author
+unless `--role subscriber` is given.
+
+`diff` compares two snapshots of the same identity, exported with the same password. It reports the
+publishers whose sequence state differs, subscribers and PSK ids added or removed, and whether one
+snapshot is further along the publisher sequences or they have diverged. It exits with status 1 when
+they differ.
+
+The password is read from the STREAMS_STATE_PASSWORD environment variable, from a key file only
+its owner can read, or else prompted for.";
+
+struct Options {
+    diff: bool,
+    files: Vec<PathBuf>,
+    role: Role,
+    password: PasswordSource,
+    json: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options> {
+    let diff = match args.next().as_deref() {
+        Some("inspect") => false,
+        Some("diff") => true,
+        Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            std::process::exit(0);
+        }
+        other => bail!("Unknown command: {}\n\n{}", other.unwrap_or_default(), USAGE),
+    };
+
+    let mut files = Vec::new();
+    let mut role = Role::Author;
+    let mut password = None;
+    let mut json = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--role" => match args.next() {
+                Some(value) => role = value.parse()?,
+                None => bail!("Missing value for --role"),
+            },
+            "--password-file" => match args.next() {
+                Some(path) => password = Some(PasswordSource::File(path.into())),
+                None => bail!("Missing value for --password-file"),
+            },
+            "--json" => json = true,
+            flag if flag.starts_with("--") => bail!("Unknown option: {}\n\n{}", flag, USAGE),
+            path => files.push(PathBuf::from(path)),
+        }
+    }
+
+    let password = match password {
+        Some(source) => source,
+        None if std::env::var_os(PASSWORD_ENV_VAR).is_some() => PasswordSource::env(),
+        None => PasswordSource::Prompt("State password: ".to_string()),
+    };
+    let expected = if diff { 2 } else { 1 };
+    if files.len() != expected {
+        bail!("Expected {} state file(s), got {}\n\n{}", expected, files.len(), USAGE);
+    }
+    Ok(Options {
+        diff,
+        files,
+        role,
+        password,
+        json,
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let options = parse_args(std::env::args().skip(1))?;
+    let password = options.password.load()?;
+    let mut summaries = Vec::new();
+    for file in &options.files {
+        let state = std::fs::read(file)?;
+        summaries.push(state::inspect(options.role, &state, &password, transports::offline()).await?);
+    }
+
+    if !options.diff {
+        if options.json {
+            println!("{}", serde_json::to_string_pretty(&summaries[0])?);
+        } else {
+            print!("{}", summaries[0]);
+        }
+        return Ok(());
+    }
+
+    let diff = state::diff(&summaries[0], &summaries[1])?;
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        print!("{}", diff);
+    }
+    if !diff.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
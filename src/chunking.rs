@@ -0,0 +1,326 @@
+use crate::{
+    payload::{decode_masked, Codec, CodecError, Format, Record},
+    publisher::Publisher,
+};
+use iota_streams::{
+    app_channels::api::tangle::{Address, Bytes, MessageContent, PublicKey, UnwrappedMessage},
+    core::Result,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    fmt,
+    path::Path,
+};
+
+/// Chunk size that keeps a packet well within the message size limit of a node
+pub const DEFAULT_CHUNK_SIZE: usize = 16 * 1024;
+
+/// Most chunks a transfer is made of, 1 GiB of content at the default chunk size. Manifests are sent by
+/// any publisher on the channel, so readers reject those claiming more before acting on them
+pub const MAX_CHUNKS: u32 = 64 * 1024;
+
+/// First packet of a transfer, describing the content the chunks that follow it make up
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// SHA-256 of the content, hex encoded. It identifies the transfer and is checked on reassembly
+    pub id: String,
+    pub name: String,
+    pub size: u64,
+    pub chunk_size: u32,
+    pub chunks: u32,
+}
+
+impl Record for Manifest {
+    const SCHEMA: &'static str = "chunking.manifest";
+    const VERSION: u16 = 1;
+}
+
+impl Manifest {
+    /// Check that the chunk count is the one the size and chunk size make for, and within [`MAX_CHUNKS`]
+    pub fn validate(&self) -> std::result::Result<(), ChunkError> {
+        let invalid = |reason: String| Err(ChunkError::InvalidManifest(reason));
+        if self.chunk_size == 0 {
+            return invalid("chunk size is 0".to_string());
+        }
+        let chunk_size = u64::from(self.chunk_size);
+        let expected = self.size / chunk_size + u64::from(self.size % chunk_size != 0);
+        if u64::from(self.chunks) != expected {
+            return invalid(format!(
+                "{} chunks of {} bytes cannot make up {} bytes",
+                self.chunks, self.chunk_size, self.size
+            ));
+        }
+        if self.chunks > MAX_CHUNKS {
+            return invalid(format!("{} chunks, more than the limit of {}", self.chunks, MAX_CHUNKS));
+        }
+        Ok(())
+    }
+
+    /// Length of the chunk at the index, `None` past the last chunk. Only meaningful for a valid manifest
+    pub fn chunk_len(&self, index: u32) -> Option<u64> {
+        if index >= self.chunks {
+            return None;
+        }
+        let start = u64::from(index) * u64::from(self.chunk_size);
+        Some((self.size - start).min(u64::from(self.chunk_size)))
+    }
+}
+
+/// A piece of the content of a transfer
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Chunk {
+    pub transfer: String,
+    pub index: u32,
+    pub data: Vec<u8>,
+}
+
+impl Record for Chunk {
+    const SCHEMA: &'static str = "chunking.chunk";
+    const VERSION: u16 = 1;
+}
+
+/// Identifies a transfer to readers: the public key of the publisher that signed its manifest, and its
+/// id, both hex encoded. Ids are public content hashes, so anyone could reuse one
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TransferKey {
+    pub publisher: String,
+    pub id: String,
+}
+
+impl TransferKey {
+    pub fn new(publisher: &PublicKey, id: &str) -> Self {
+        TransferKey {
+            publisher: hex::encode(publisher.as_bytes()),
+            id: id.to_string(),
+        }
+    }
+}
+
+/// Links of a sent transfer
+#[derive(Clone, Debug)]
+pub struct Transfer {
+    pub manifest: Manifest,
+    pub manifest_link: Address,
+    pub chunk_links: Vec<Address>,
+}
+
+impl Transfer {
+    /// Link of the last packet of the transfer, to attach whatever comes next to
+    pub fn last_link(&self) -> &Address {
+        self.chunk_links.last().unwrap_or(&self.manifest_link)
+    }
+}
+
+pub fn content_hash(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// Send the content as a manifest linked to `link_to`, followed by a chain of chunk packets of at
+/// most `chunk_size` bytes each. Manifest and chunks are masked payloads of signed packets
+pub async fn send_chunked<P: Publisher>(
+    publisher: &mut P,
+    link_to: &Address,
+    name: &str,
+    data: &[u8],
+    chunk_size: usize,
+) -> Result<Transfer> {
+    anyhow::ensure!(chunk_size > 0, "Chunk size must be greater than 0");
+    let manifest = Manifest {
+        id: content_hash(data),
+        name: name.to_string(),
+        size: u64::try_from(data.len())?,
+        chunk_size: u32::try_from(chunk_size)
+            .map_err(|_| anyhow::anyhow!("Chunk size of {} bytes does not fit a manifest", chunk_size))?,
+        chunks: u32::try_from(data.chunks(chunk_size).len())
+            .map_err(|_| anyhow::anyhow!("Content of {} bytes makes too many chunks", data.len()))?,
+    };
+    // Readers would reject the transfer anyway
+    manifest.validate()?;
+
+    let (manifest_link, _seq_link) = publisher
+        .send_signed_packet(link_to, &Bytes::default(), &Codec::new(Format::Json).encode(&manifest)?)
+        .await?;
+
+    let codec = Codec::new(Format::Bincode);
+    let mut chunk_links = Vec::new();
+    let mut prev_msg_link = manifest_link.clone();
+    for (index, data) in (0..manifest.chunks).zip(data.chunks(chunk_size)) {
+        let chunk = Chunk {
+            transfer: manifest.id.clone(),
+            index,
+            data: data.to_vec(),
+        };
+        let (msg_link, _seq_link) = publisher
+            .send_signed_packet(&prev_msg_link, &Bytes::default(), &codec.encode(&chunk)?)
+            .await?;
+        chunk_links.push(msg_link.clone());
+        prev_msg_link = msg_link;
+    }
+
+    Ok(Transfer {
+        manifest,
+        manifest_link,
+        chunk_links,
+    })
+}
+
+/// Send the contents of a file, named after the file
+pub async fn send_file<P: Publisher, F: AsRef<Path>>(
+    publisher: &mut P,
+    link_to: &Address,
+    path: F,
+    chunk_size: usize,
+) -> Result<Transfer> {
+    let path = path.as_ref();
+    let data = std::fs::read(path)?;
+    let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    send_chunked(publisher, link_to, &name, &data, chunk_size).await
+}
+
+/// Reasons a transfer could not be reassembled
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChunkError {
+    /// Chunks of the transfer were retrieved, but not its manifest
+    MissingManifest,
+    /// Indexes of the chunks that were not retrieved
+    MissingChunks(Vec<u32>),
+    /// The manifest does not describe a transfer that could be reassembled, see [`Manifest::validate`]
+    InvalidManifest(String),
+    /// The reassembled content does not match the manifest. The chunks are dropped, so that the transfer
+    /// can be reassembled from chunks sent again
+    HashMismatch { expected: String, found: String },
+}
+
+impl fmt::Display for ChunkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChunkError::MissingManifest => write!(f, "Manifest of the transfer was not retrieved"),
+            ChunkError::MissingChunks(missing) => write!(f, "Chunks {:?} of the transfer were not retrieved", missing),
+            ChunkError::InvalidManifest(reason) => write!(f, "Invalid manifest: {}", reason),
+            ChunkError::HashMismatch { expected, found } => {
+                write!(f, "Reassembled content hashes to {}, expected {}", found, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChunkError {}
+
+#[derive(Default)]
+struct Pending {
+    manifest: Option<Manifest>,
+    chunks: BTreeMap<u32, Vec<u8>>,
+}
+
+impl Pending {
+    // Chunks retrieved before the manifest are only bounded by MAX_CHUNKS, those that do not fit
+    // the manifest are dropped once it is known
+    fn accepts(&self, chunk: &Chunk) -> bool {
+        match &self.manifest {
+            Some(manifest) => fits(manifest, chunk.index, &chunk.data),
+            None => chunk.index < MAX_CHUNKS,
+        }
+    }
+}
+
+fn fits(manifest: &Manifest, index: u32, data: &[u8]) -> bool {
+    manifest.chunk_len(index) == u64::try_from(data.len()).ok()
+}
+
+/// Collects manifests and chunks from retrieved messages, across as many fetches as it takes, and
+/// reassembles the transfers they make up. Other messages are ignored.
+///
+/// Manifests and chunks are only taken from signed packets, and a chunk only counts towards the
+/// transfers of its own signer, so other publishers on the channel cannot inject chunks into a transfer
+#[derive(Default)]
+pub struct Assembler {
+    transfers: HashMap<TransferKey, Pending>,
+}
+
+impl Assembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect the manifests and chunks among the retrieved messages. Invalid manifests are ignored, as
+    /// are chunks out of the range or of another length than their manifest says. A chunk retrieved
+    /// twice keeps the data it was first retrieved with
+    pub fn push(&mut self, retrieved: &[UnwrappedMessage]) {
+        for msg in retrieved {
+            let publisher = match &msg.body {
+                MessageContent::SignedPacket { pk, .. } => hex::encode(pk.as_bytes()),
+                _ => continue,
+            };
+            match decode_masked::<Chunk>(msg) {
+                Ok(chunk) => {
+                    let key = TransferKey {
+                        publisher,
+                        id: chunk.transfer.clone(),
+                    };
+                    let pending = self.transfers.entry(key).or_default();
+                    if pending.accepts(&chunk) {
+                        pending.chunks.entry(chunk.index).or_insert(chunk.data);
+                    }
+                }
+                Err(CodecError::SchemaMismatch { .. }) => {
+                    if let Ok(manifest) = decode_masked::<Manifest>(msg) {
+                        if manifest.validate().is_err() {
+                            continue;
+                        }
+                        let key = TransferKey {
+                            publisher,
+                            id: manifest.id.clone(),
+                        };
+                        let pending = self.transfers.entry(key).or_default();
+                        if pending.manifest.is_none() {
+                            pending.chunks.retain(|index, data| fits(&manifest, *index, data));
+                            pending.manifest = Some(manifest);
+                        }
+                    }
+                }
+                Err(_) => {}
+            }
+        }
+    }
+
+    /// Transfers seen so far, complete or not
+    pub fn transfers(&self) -> Vec<&TransferKey> {
+        self.transfers.keys().collect()
+    }
+
+    pub fn manifest(&self, key: &TransferKey) -> Option<&Manifest> {
+        self.transfers.get(key).and_then(|pending| pending.manifest.as_ref())
+    }
+
+    /// Indexes of the chunks of the transfer that have not been retrieved
+    pub fn missing(&self, key: &TransferKey) -> std::result::Result<Vec<u32>, ChunkError> {
+        let pending = self.transfers.get(key).ok_or(ChunkError::MissingManifest)?;
+        let manifest = pending.manifest.as_ref().ok_or(ChunkError::MissingManifest)?;
+        Ok((0..manifest.chunks)
+            .filter(|index| !pending.chunks.contains_key(index))
+            .collect())
+    }
+
+    /// Reassemble the content of the transfer and check it against its hash
+    pub fn finish(&mut self, key: &TransferKey) -> std::result::Result<Vec<u8>, ChunkError> {
+        let missing = self.missing(key)?;
+        if !missing.is_empty() {
+            return Err(ChunkError::MissingChunks(missing));
+        }
+
+        let pending = self.transfers.get_mut(key).ok_or(ChunkError::MissingManifest)?;
+        let data: Vec<u8> = pending.chunks.values().flat_map(|data| data.iter().copied()).collect();
+        let found = content_hash(&data);
+        if found != key.id {
+            pending.chunks.clear();
+            return Err(ChunkError::HashMismatch {
+                expected: key.id.clone(),
+                found,
+            });
+        }
+        Ok(data)
+    }
+}
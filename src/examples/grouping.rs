@@ -0,0 +1,64 @@
+use iota_streams::app_channels::api::tangle::{MessageContent, PublicKey, UnwrappedMessage};
+use std::collections::HashMap;
+
+/// Retrieved messages grouped by the publisher that signed them, in the order they were retrieved
+#[derive(Default)]
+pub struct PublisherGroups {
+    publishers: HashMap<Vec<u8>, Vec<UnwrappedMessage>>,
+    /// Signed packets from publishers that were not expected
+    pub unknown: Vec<UnwrappedMessage>,
+    /// Messages without a publisher signature, i.e. keyloads, sequencing messages, tagged packets or
+    /// messages the reader could not unwrap
+    pub unsigned: Vec<UnwrappedMessage>,
+}
+
+impl PublisherGroups {
+    /// Messages signed by the publisher
+    pub fn get(&self, publisher: &PublicKey) -> &[UnwrappedMessage] {
+        self.publishers
+            .get(&publisher.as_bytes()[..])
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Remove and return the messages signed by the publisher
+    pub fn take(&mut self, publisher: &PublicKey) -> Vec<UnwrappedMessage> {
+        self.publishers.remove(&publisher.as_bytes()[..]).unwrap_or_default()
+    }
+
+    /// Number of expected publishers that signed at least one of the messages
+    pub fn len(&self) -> usize {
+        self.publishers.values().filter(|msgs| !msgs.is_empty()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Group retrieved messages by the expected publisher that signed them. Any number of publishers
+/// can be expected; packets signed by anyone else and messages without a signature are kept apart
+pub fn group_by_publisher(retrieved: Vec<UnwrappedMessage>, publishers: &[PublicKey]) -> PublisherGroups {
+    let mut groups = PublisherGroups {
+        publishers: publishers
+            .iter()
+            .map(|pk| (pk.as_bytes().to_vec(), Vec::new()))
+            .collect(),
+        ..PublisherGroups::default()
+    };
+
+    for msg in retrieved {
+        let signer = match &msg.body {
+            MessageContent::SignedPacket { pk, .. } => Some(pk.as_bytes().to_vec()),
+            _ => None,
+        };
+        match signer {
+            Some(signer) => match groups.publishers.get_mut(&signer) {
+                Some(msgs) => msgs.push(msg),
+                None => groups.unknown.push(msg),
+            },
+            None => groups.unsigned.push(msg),
+        }
+    }
+    groups
+}
@@ -1,53 +1,37 @@
 use iota_streams::{
-    app::transport::tangle::client::Client,
-    app_channels::api::tangle::{
-        Address, Author, Bytes, ChannelType, MessageContent, PublicKey, Subscriber,
-        UnwrappedMessage,
-    },
-    core::{println, Result},
+    app_channels::api::tangle::{Address, Author, Bytes, ChannelType, PublicKey, Subscriber, Transport},
+    core::Result,
 };
 
-use crate::examples::{verify_messages, ALPH9};
-use rand::Rng;
+use crate::examples::{group_by_publisher, verify_messages, ExampleReport, SeedProvider};
 use iota_streams::app::message::HasLink;
 
 /**
  * In this example, the Author will generate a new branch for each Subscriber, and each Subscriber
  * will only post/read from their individual branches
 */
-pub async fn example(node_url: &str) -> Result<()> {
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider) -> Result<ExampleReport> {
     // Generate a unique seed for the author
-    let seed: &str = &(0..81)
-        .map(|_| {
-            ALPH9
-                .chars()
-                .nth(rand::thread_rng().gen_range(0, 27))
-                .unwrap()
-        })
-        .collect::<String>();
-
-    // Create the Transport Client
-    let client = Client::new_from_url(node_url);
+    let seed: &str = &seeds.author_seed();
 
     // Generate an Author
-    let mut author = Author::new(seed, ChannelType::MultiBranch, client.clone());
+    let mut author = Author::new(seed, ChannelType::MultiBranch, transport.clone());
+
+    // Keep a report of everything the example sends and retrieves
+    let mut report = ExampleReport::new(ChannelType::MultiBranch);
 
     // Create the channel with an announcement message. Make sure to save the resulting link somewhere,
     let announcement_link = author.send_announce().await?;
     // This link acts as a root for the channel itself
-    let ann_link_string = announcement_link.to_string();
-    println!(
-        "Announcement Link: {}\nTangle Index: {:#}\n",
-        ann_link_string, announcement_link.to_msg_index()
-    );
+    report.announcement(&announcement_link);
 
     // ------------------------------------------------------------------
     // In their own separate instances generate the subscriber(s) that will be attaching to the channel
 
-    let mut subscriber_a = Subscriber::new("SubscriberA", client.clone());
-    let mut subscriber_b = Subscriber::new("SubscriberB", client.clone());
-    let mut subscriber_c = Subscriber::new("SubscriberC", client.clone());
-    let mut subscriber_d = Subscriber::new("SubscriberD", client);
+    let mut subscriber_a = Subscriber::new("SubscriberA", transport.clone());
+    let mut subscriber_b = Subscriber::new("SubscriberB", transport.clone());
+    let mut subscriber_c = Subscriber::new("SubscriberC", transport.clone());
+    let mut subscriber_d = Subscriber::new("SubscriberD", transport);
 
     // Generate an Address object from the provided announcement link from the Author
     let ann_address = Address::from_bytes(&announcement_link.to_bytes());
@@ -64,28 +48,10 @@ pub async fn example(node_url: &str) -> Result<()> {
     let subscribe_msg_c = subscriber_c.send_subscribe(&ann_address).await?;
     let subscribe_msg_d = subscriber_d.send_subscribe(&ann_address).await?;
 
-    // These are the subscription links that should be provided to the Author to complete subscription
-    let sub_msg_a_str = subscribe_msg_a.to_string();
-    let sub_msg_b_str = subscribe_msg_b.to_string();
-    let sub_msg_c_str = subscribe_msg_c.to_string();
-    let sub_msg_d_str = subscribe_msg_d.to_string();
-
-    println!(
-        "Subscription msgs:\n\tSubscriber A: {}\n\tTangle Index: {:#}\n",
-        sub_msg_a_str, subscribe_msg_a.to_msg_index()
-    );
-    println!(
-        "\tSubscriber B: {}\n\tTangle Index: {:#}\n",
-        sub_msg_b_str, subscribe_msg_b.to_msg_index()
-    );
-    println!(
-        "\tSubscriber C: {}\n\tTangle Index: {:#}\n",
-        sub_msg_c_str, subscribe_msg_c.to_msg_index()
-    );
-    println!(
-        "\tSubscriber D: {}\n\tTangle Index: {:#}\n",
-        sub_msg_d_str, subscribe_msg_d.to_msg_index()
-    );
+    report.subscription("Subscriber A", &subscribe_msg_a);
+    report.subscription("Subscriber B", &subscribe_msg_b);
+    report.subscription("Subscriber C", &subscribe_msg_c);
+    report.subscription("Subscriber D", &subscribe_msg_d);
 
     // Fetch subscriber public keys (for use by author in issuing a keyload)
     let sub_a_pk = subscriber_a.get_public_key().as_bytes();
@@ -125,23 +91,16 @@ pub async fn example(node_url: &str) -> Result<()> {
     // generate a new branch. These public keys can be converted to Identifiers with a simple From/
     // Into conversion. This will return a tuple containing the message links. The first is the
     // message link itself, the second is the sequencing message link.
-    let (keyload_a_link, _seq_a_link) =
+    let (keyload_a_link, seq_a_link) =
         author.send_keyload(&announcement_link, &vec![pks[0].into(), pks[1].into()]).await?;
-    println!(
-        "\nSent Keyload for Sub A and B: {}, tangle index: {:#}",
-        keyload_a_link,
-        _seq_a_link.unwrap()
-    );
+    report.keyload("for Sub A and B", &keyload_a_link, seq_a_link.as_ref());
 
     // Author will send the second Keyload with the public key of Subscribers C and D (also linked
     // to the announcement message) to generate another new branch
-    let (keyload_b_link, _seq_b_link) =
+    let (keyload_b_link, seq_b_link) =
         author.send_keyload(&announcement_link, &vec![pks[2].into(), pks[3].into()]).await?;
-    println!(
-        "\nSent Keyload for Sub C and D: {}, tangle index: {:#}\n",
-        keyload_b_link,
-        _seq_b_link.unwrap()
-    );
+    report.keyload("for Sub C and D", &keyload_b_link, seq_b_link.as_ref());
+    report.phase("setup");
 
     // Subscribers A and B will now send encrypted messages in an alternating chain attached to Keyload A
     let msg_inputs_a = vec![
@@ -178,8 +137,7 @@ pub async fn example(node_url: &str) -> Result<()> {
             &Bytes::default(),
             &Bytes(msg_inputs_a[i].as_bytes().to_vec()),
         ).await?;
-        let seq_link = seq_link.unwrap();
-        println!("Sent msg from Sub A: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("from Sub A", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
 
         // Sub B Sends
@@ -189,8 +147,7 @@ pub async fn example(node_url: &str) -> Result<()> {
             &Bytes::default(),
             &Bytes(msg_inputs_b[i].as_bytes().to_vec()),
         ).await?;
-        let seq_link = seq_link.unwrap();
-        println!("Sent msg from Sub B: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("from Sub B", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
 
@@ -225,8 +182,7 @@ pub async fn example(node_url: &str) -> Result<()> {
             &Bytes::default(),
             &Bytes(msg_inputs_c[i].as_bytes().to_vec()),
         ).await?;
-        let seq_link = seq_link.unwrap();
-        println!("Sent msg from Sub C: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("from Sub C", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
 
         // Sub D Sends
@@ -236,61 +192,23 @@ pub async fn example(node_url: &str) -> Result<()> {
             &Bytes::default(),
             &Bytes(msg_inputs_d[i].as_bytes().to_vec()),
         ).await?;
-        let seq_link = seq_link.unwrap();
-        println!("Sent msg from Sub D: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("from Sub D", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
 
+    report.phase("publish");
+
     // -----------------------------------------------------------------------------
     // Author can now fetch these messages
-    let mut retrieved = author.fetch_all_next_msgs().await;
-    println!("\nAuthor found {} messages", retrieved.len());
-    let mut retrieved_lists = split_retrieved(&mut retrieved, pks);
-    println!("\nVerifying message retrieval: Author");
-    verify_messages(&msg_inputs_a, retrieved_lists.remove(0))?;
-    verify_messages(&msg_inputs_b, retrieved_lists.remove(0))?;
-    verify_messages(&msg_inputs_c, retrieved_lists.remove(0))?;
-    verify_messages(&msg_inputs_d, retrieved_lists.remove(0))?;
-
-    Ok(())
-}
-
-fn split_retrieved(
-    retrieved: &mut Vec<UnwrappedMessage>,
-    pks: Vec<PublicKey>,
-) -> Vec<Vec<UnwrappedMessage>> {
-    let mut retrieved_msgs_a = Vec::new();
-    let mut retrieved_msgs_b = Vec::new();
-    let mut retrieved_msgs_c = Vec::new();
-    let mut retrieved_msgs_d = Vec::new();
-
-    // Sort messages by sender
-    for _ in 0..retrieved.len() {
-        let msg = retrieved.remove(0);
-        let pk = match msg.body {
-            MessageContent::SignedPacket {
-                pk,
-                public_payload: _,
-                masked_payload: _,
-            } => pk,
-            _ => PublicKey::default(),
-        };
-
-        if pk == pks[0] {
-            retrieved_msgs_a.push(msg);
-        } else if pk == pks[1] {
-            retrieved_msgs_b.push(msg);
-        } else if pk == pks[2] {
-            retrieved_msgs_c.push(msg);
-        } else {
-            retrieved_msgs_d.push(msg);
-        }
-    }
-
-    vec![
-        retrieved_msgs_a,
-        retrieved_msgs_b,
-        retrieved_msgs_c,
-        retrieved_msgs_d,
-    ]
+    let retrieved = author.fetch_all_next_msgs().await;
+    report.retrieved("Author", retrieved.len());
+    let mut groups = group_by_publisher(retrieved, &pks);
+    verify_messages(&msg_inputs_a, groups.take(&pks[0]))?;
+    verify_messages(&msg_inputs_b, groups.take(&pks[1]))?;
+    verify_messages(&msg_inputs_c, groups.take(&pks[2]))?;
+    verify_messages(&msg_inputs_d, groups.take(&pks[3]))?;
+    verify_messages(&[], groups.unknown)?;
+    report.phase("fetch");
+
+    Ok(report)
 }
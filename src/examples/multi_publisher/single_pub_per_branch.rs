@@ -1,55 +1,37 @@
 use iota_streams::{
-    app::{
-        message::HasLink,
-        transport::tangle::client::Client
-    },
-    app_channels::api::tangle::{
-        Address, Author, Bytes, ChannelType, MessageContent, PublicKey, Subscriber,
-        UnwrappedMessage,
-    },
-    core::{println, Result},
+    app::message::HasLink,
+    app_channels::api::tangle::{Address, Author, Bytes, ChannelType, PublicKey, Subscriber, Transport},
+    core::Result,
 };
 use core::str::FromStr;
 
-use crate::examples::{verify_messages, ALPH9};
-use rand::Rng;
+use crate::examples::{group_by_publisher, verify_messages, ExampleReport, SeedProvider};
 
 /**
  * In this example, the Author will generate a new branch for each Subscriber, and each Subscriber
  * will only post/read from their individual branches
 */
-pub async fn example(node_url: &str) -> Result<()> {
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider) -> Result<ExampleReport> {
     // Generate a unique seed for the author
-    let seed: &str = &(0..81)
-        .map(|_| {
-            ALPH9
-                .chars()
-                .nth(rand::thread_rng().gen_range(0, 27))
-                .unwrap()
-        })
-        .collect::<String>();
-
-    // Create the Transport Client
-    let client = Client::new_from_url(node_url);
+    let seed: &str = &seeds.author_seed();
 
     // Generate an Author
-    let mut author = Author::new(seed, ChannelType::MultiBranch, client.clone());
+    let mut author = Author::new(seed, ChannelType::MultiBranch, transport.clone());
+
+    // Keep a report of everything the example sends and retrieves
+    let mut report = ExampleReport::new(ChannelType::MultiBranch);
 
     // Create the channel with an announcement message. Make sure to save the resulting link somewhere,
     let announcement_link = author.send_announce().await?;
     // This link acts as a root for the channel itself
-    let ann_link_string = announcement_link.to_string();
-    println!(
-        "Announcement Link: {}\nTangle Index: {:#}\n",
-        ann_link_string, announcement_link.to_msg_index()
-    );
+    report.announcement(&announcement_link);
 
     // ------------------------------------------------------------------
     // In their own separate instances generate the subscriber(s) that will be attaching to the channel
 
-    let mut subscriber_a = Subscriber::new("SubscriberA", client.clone());
-    let mut subscriber_b = Subscriber::new("SubscriberB", client.clone());
-    let mut subscriber_c = Subscriber::new("SubscriberC", client);
+    let mut subscriber_a = Subscriber::new("SubscriberA", transport.clone());
+    let mut subscriber_b = Subscriber::new("SubscriberB", transport.clone());
+    let mut subscriber_c = Subscriber::new("SubscriberC", transport);
 
     // Generate an Address object from the provided announcement link string from the Author
     let ann_address = Address::from_bytes(&announcement_link.to_bytes());
@@ -65,22 +47,10 @@ pub async fn example(node_url: &str) -> Result<()> {
     let subscribe_msg_c = subscriber_c.send_subscribe(&ann_address).await?;
 
     // These are the subscription links that should be provided to the Author to complete subscription
-    let sub_msg_a_str = subscribe_msg_a.to_string();
     let sub_msg_b_str = subscribe_msg_b.to_string();
-    let sub_msg_c_str = subscribe_msg_c.to_string();
-
-    println!(
-        "Subscription msgs:\n\tSubscriber A: {}\n\tTangle Index: {:#}\n",
-        sub_msg_a_str, subscribe_msg_a.to_msg_index()
-    );
-    println!(
-        "\tSubscriber B: {}\n\tTangle Index: {:#}\n",
-        sub_msg_b_str, subscribe_msg_b.to_msg_index()
-    );
-    println!(
-        "\tSubscriber C: {}\n\tTangle Index: {:#}\n",
-        sub_msg_c_str, subscribe_msg_c.to_msg_index()
-    );
+    report.subscription("Subscriber A", &subscribe_msg_a);
+    report.subscription("Subscriber B", &subscribe_msg_b);
+    report.subscription("Subscriber C", &subscribe_msg_c);
 
     // Fetch subscriber public keys (for use by author in issuing a keyload)
     let sub_a_pk = subscriber_a.get_public_key().as_bytes();
@@ -113,39 +83,28 @@ pub async fn example(node_url: &str) -> Result<()> {
     // Author sends keyload with the public key of Sub A (linked to announcement message) to generate
     // a new branch. This will return a tuple containing the message links. The first is the message
     // link itself, the second is a sequencing message.
-    let (keyload_a_link, _seq_a_link) = author.send_keyload(
+    let (keyload_a_link, seq_a_link) = author.send_keyload(
         &announcement_link,
         &vec![PublicKey::from_bytes(sub_a_pk)?.into()],
     ).await?;
-    println!(
-        "\nSent Keyload for Sub A: {}, tangle index: {:#}",
-        keyload_a_link,
-        _seq_a_link.unwrap()
-    );
+    report.keyload("for Sub A", &keyload_a_link, seq_a_link.as_ref());
 
     // Author will send the second Keyload with the public key of Subscriber B (also linked to the
     // announcement message) to generate another new branch
-    let (keyload_b_link, _seq_b_link) = author.send_keyload(
+    let (keyload_b_link, seq_b_link) = author.send_keyload(
         &announcement_link,
         &vec![PublicKey::from_bytes(sub_b_pk)?.into()],
     ).await?;
-    println!(
-        "\nSent Keyload for Sub B: {}, tangle index: {:#}",
-        keyload_b_link,
-        _seq_b_link.unwrap()
-    );
+    report.keyload("for Sub B", &keyload_b_link, seq_b_link.as_ref());
 
     // Author will send the third Keyload with the public key of Subscriber C (also linked to the
     // announcement message) to generate another new branch
-    let (keyload_c_link, _seq_c_link) = author.send_keyload(
+    let (keyload_c_link, seq_c_link) = author.send_keyload(
         &announcement_link,
         &vec![PublicKey::from_bytes(sub_c_pk)?.into()],
     ).await?;
-    println!(
-        "\nSent Keyload for Sub C: {}, tangle index: {:#}\n",
-        keyload_c_link,
-        _seq_c_link.unwrap()
-    );
+    report.keyload("for Sub C", &keyload_c_link, seq_c_link.as_ref());
+    report.phase("setup");
 
     // Before sending any messages, a publisher in a multi publisher channel should sync their state
     // to ensure they are up to date
@@ -174,8 +133,7 @@ pub async fn example(node_url: &str) -> Result<()> {
             &Bytes::default(),
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
-        let seq_link = seq_link.unwrap();
-        println!("Sent msg from Sub A: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("from Sub A", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
 
@@ -200,8 +158,7 @@ pub async fn example(node_url: &str) -> Result<()> {
             &Bytes::default(),
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
-        let seq_link = seq_link.unwrap();
-        println!("Sent msg from Sub B: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("from Sub B", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
 
@@ -226,56 +183,22 @@ pub async fn example(node_url: &str) -> Result<()> {
             &Bytes::default(),
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
-        let seq_link = seq_link.unwrap();
-        println!("Sent msg from Sub C: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("from Sub C", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
 
+    report.phase("publish");
+
     // -----------------------------------------------------------------------------
     // Author can now fetch these messages
-    let mut retrieved = author.fetch_all_next_msgs().await;
-    println!("\nFound {} msgs", retrieved.len());
-    let (retrieveda, retrievedb, retrievedc) = split_retrieved(&mut retrieved, pks);
-    println!("\nVerifying message retrieval: Author");
-    verify_messages(&msg_inputs_a, retrieveda)?;
-    verify_messages(&msg_inputs_b, retrievedb)?;
-    verify_messages(&msg_inputs_c, retrievedc)?;
-
-    Ok(())
-}
-
-fn split_retrieved(
-    retrieved: &mut Vec<UnwrappedMessage>,
-    pks: Vec<PublicKey>,
-) -> (
-    Vec<UnwrappedMessage>,
-    Vec<UnwrappedMessage>,
-    Vec<UnwrappedMessage>,
-) {
-    let mut retrieved_msgs_a = Vec::new();
-    let mut retrieved_msgs_b = Vec::new();
-    let mut retrieved_msgs_c = Vec::new();
-
-    // Sort messages by sender
-    for _ in 0..retrieved.len() {
-        let msg = retrieved.remove(0);
-        let pk = match msg.body {
-            MessageContent::SignedPacket {
-                pk,
-                public_payload: _,
-                masked_payload: _,
-            } => pk,
-            _ => PublicKey::default(),
-        };
-
-        if pk == pks[0] {
-            retrieved_msgs_a.push(msg);
-        } else if pk == pks[1] {
-            retrieved_msgs_b.push(msg);
-        } else {
-            retrieved_msgs_c.push(msg);
-        }
-    }
-
-    (retrieved_msgs_a, retrieved_msgs_b, retrieved_msgs_c)
+    let retrieved = author.fetch_all_next_msgs().await;
+    report.retrieved("Author", retrieved.len());
+    let mut groups = group_by_publisher(retrieved, &pks);
+    verify_messages(&msg_inputs_a, groups.take(&pks[0]))?;
+    verify_messages(&msg_inputs_b, groups.take(&pks[1]))?;
+    verify_messages(&msg_inputs_c, groups.take(&pks[2]))?;
+    verify_messages(&[], groups.unknown)?;
+    report.phase("fetch");
+
+    Ok(report)
 }
@@ -0,0 +1,144 @@
+use iota_streams::app_channels::api::tangle::{Address, ChannelType};
+use serde::Serialize;
+use std::{fmt, time::Instant};
+
+/// Name of a channel type, as used in reports
+pub fn channel_type_name(channel_type: &ChannelType) -> &'static str {
+    match channel_type {
+        ChannelType::SingleBranch => "SingleBranch",
+        ChannelType::MultiBranch => "MultiBranch",
+        ChannelType::SingleDepth => "SingleDepth",
+    }
+}
+
+/// A message link along with its tangle index, and the link of its sequencing message if one was sent
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LinkReport {
+    pub label: String,
+    pub link: String,
+    pub index: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seq_link: Option<String>,
+}
+
+impl LinkReport {
+    pub fn new(label: &str, link: &Address, seq_link: Option<&Address>) -> Self {
+        LinkReport {
+            label: label.to_string(),
+            link: link.to_string(),
+            index: format!("{:#}", link.to_msg_index()),
+            seq_link: seq_link.map(|seq| seq.to_string()),
+        }
+    }
+}
+
+/// Number of messages a reader retrieved
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RetrievalReport {
+    pub reader: String,
+    pub count: usize,
+}
+
+/// Time spent in a phase of an example
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PhaseTiming {
+    pub phase: String,
+    pub millis: f64,
+}
+
+/// Structured account of an example run: the channel it generated, the links of everything it sent,
+/// how many messages were retrieved by whom, and how long each phase took
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ExampleReport {
+    pub channel_type: Option<String>,
+    pub announcement: Option<LinkReport>,
+    pub subscriptions: Vec<LinkReport>,
+    pub keyloads: Vec<LinkReport>,
+    pub messages: Vec<LinkReport>,
+    pub retrieved: Vec<RetrievalReport>,
+    pub timings: Vec<PhaseTiming>,
+    pub notes: Vec<String>,
+    #[serde(skip)]
+    phase_start: Option<Instant>,
+}
+
+impl ExampleReport {
+    pub fn new(channel_type: ChannelType) -> Self {
+        ExampleReport {
+            channel_type: Some(channel_type_name(&channel_type).to_string()),
+            phase_start: Some(Instant::now()),
+            ..ExampleReport::default()
+        }
+    }
+
+    pub fn announcement(&mut self, link: &Address) {
+        self.announcement = Some(LinkReport::new("Announcement", link, None));
+    }
+
+    pub fn subscription(&mut self, subscriber: &str, link: &Address) {
+        self.subscriptions.push(LinkReport::new(subscriber, link, None));
+    }
+
+    pub fn keyload(&mut self, label: &str, link: &Address, seq_link: Option<&Address>) {
+        self.keyloads.push(LinkReport::new(label, link, seq_link));
+    }
+
+    pub fn message(&mut self, label: &str, link: &Address, seq_link: Option<&Address>) {
+        self.messages.push(LinkReport::new(label, link, seq_link));
+    }
+
+    pub fn retrieved(&mut self, reader: &str, count: usize) {
+        self.retrieved.push(RetrievalReport {
+            reader: reader.to_string(),
+            count,
+        });
+    }
+
+    pub fn note<S: Into<String>>(&mut self, note: S) {
+        self.notes.push(note.into());
+    }
+
+    /// Record the time elapsed since the previous phase ended (or since the report was created)
+    pub fn phase(&mut self, phase: &str) {
+        let now = Instant::now();
+        let start = self.phase_start.replace(now).unwrap_or(now);
+        self.timings.push(PhaseTiming {
+            phase: phase.to_string(),
+            millis: (now - start).as_secs_f64() * 1000.0,
+        });
+    }
+}
+
+impl fmt::Display for ExampleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(channel_type) = &self.channel_type {
+            writeln!(f, "Channel Type: {}", channel_type)?;
+        }
+        if let Some(announcement) = &self.announcement {
+            writeln!(f, "Announcement Link: {}\nTangle Index: {}", announcement.link, announcement.index)?;
+        }
+        for subscription in &self.subscriptions {
+            writeln!(
+                f,
+                "Subscription msg from {}: {}, tangle index: {}",
+                subscription.label, subscription.link, subscription.index
+            )?;
+        }
+        for keyload in &self.keyloads {
+            writeln!(f, "Keyload {}: {}, tangle index: {}", keyload.label, keyload.link, keyload.index)?;
+        }
+        for msg in &self.messages {
+            writeln!(f, "Sent msg {}: {}, tangle index: {}", msg.label, msg.link, msg.index)?;
+        }
+        for retrieved in &self.retrieved {
+            writeln!(f, "{} retrieved {} msgs", retrieved.reader, retrieved.count)?;
+        }
+        for note in &self.notes {
+            writeln!(f, "{}", note)?;
+        }
+        for timing in &self.timings {
+            writeln!(f, "{}: {:.2}ms", timing.phase, timing.millis)?;
+        }
+        Ok(())
+    }
+}
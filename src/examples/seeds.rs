@@ -0,0 +1,78 @@
+use crate::examples::ALPH9;
+use iota_streams::core::Result;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Environment variable holding the seed used to run examples deterministically
+pub const SEED_ENV_VAR: &str = "STREAMS_EXAMPLES_SEED";
+
+/// Source of the author seeds and pre shared keys used by the examples.
+///
+/// In deterministic mode every value is drawn from an RNG seeded with the provided number, so a run
+/// can be reproduced bit-for-bit with the same author identities and keys. In random mode values
+/// are drawn from entropy and runs cannot be reproduced.
+pub struct SeedProvider {
+    rng: StdRng,
+    seed: Option<u64>,
+}
+
+impl SeedProvider {
+    pub fn random() -> Self {
+        SeedProvider {
+            rng: StdRng::from_entropy(),
+            seed: None,
+        }
+    }
+
+    pub fn deterministic(seed: u64) -> Self {
+        SeedProvider {
+            rng: StdRng::seed_from_u64(seed),
+            seed: Some(seed),
+        }
+    }
+
+    /// Deterministic provider if `STREAMS_EXAMPLES_SEED` is set, random provider otherwise
+    pub fn from_env() -> Result<Self> {
+        match std::env::var(SEED_ENV_VAR) {
+            Ok(seed) => Ok(Self::deterministic(seed.trim().parse()?)),
+            Err(_) => Ok(Self::random()),
+        }
+    }
+
+    /// The seed of a deterministic provider
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Provider for a single named example. In deterministic mode it is derived from the seed and
+    /// the name only, so an example yields the same values whether it is run alone or in a suite.
+    pub fn for_example(&self, name: &str) -> Self {
+        match self.seed {
+            Some(seed) => Self::deterministic(seed ^ fnv1a(name.as_bytes())),
+            None => Self::random(),
+        }
+    }
+
+    /// A new 81 character tryte seed for an `Author`
+    pub fn author_seed(&mut self) -> String {
+        (0..81)
+            .map(|_| ALPH9.chars().nth(self.rng.gen_range(0, 27)).unwrap())
+            .collect()
+    }
+
+    /// A new key to be used as a Pre Shared Key
+    pub fn psk_key(&mut self) -> [u8; 32] {
+        self.rng.gen()
+    }
+
+    /// A new random number, i.e. to seed a provider for a nested run
+    pub fn next_u64(&mut self) -> u64 {
+        self.rng.gen()
+    }
+}
+
+// Stable across platforms and compiler versions, unlike the std hasher
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
@@ -0,0 +1,84 @@
+use crate::examples::{verify_messages, verify_unreadable};
+use anyhow::{anyhow, ensure};
+use iota_streams::{
+    app_channels::api::tangle::{Address, MessageContent, UnwrappedMessage},
+    core::Result,
+};
+
+/// Whether a reader could read the packets of a segment
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// At least one packet could be read
+    Granted,
+    /// Packets were found, but none could be read
+    Denied,
+    /// The segment holds no packets to tell
+    Unknown,
+}
+
+/// The messages retrieved after a keyload, up to the next keyload
+pub struct Segment {
+    /// Link of the keyload opening the segment, `None` for messages retrieved before any keyload
+    pub keyload: Option<Address>,
+    pub access: Access,
+    pub messages: Vec<UnwrappedMessage>,
+}
+
+/// Split a fetched message stream at every keyload. Readers are handed every keyload, including
+/// those they are not part of, so the segments follow the keyloads sent regardless of access
+pub fn split_at_keyloads(retrieved: Vec<UnwrappedMessage>) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = Vec::new();
+    for msg in retrieved {
+        if let MessageContent::Keyload = msg.body {
+            segments.push(Segment {
+                keyload: Some(msg.link),
+                access: Access::Unknown,
+                messages: Vec::new(),
+            });
+            continue;
+        }
+
+        if segments.is_empty() {
+            segments.push(Segment {
+                keyload: None,
+                access: Access::Unknown,
+                messages: Vec::new(),
+            });
+        }
+        if let Some(segment) = segments.last_mut() {
+            segment.access = match (&msg.body, segment.access) {
+                (MessageContent::SignedPacket { .. }, _) | (MessageContent::TaggedPacket { .. }, _) => Access::Granted,
+                (MessageContent::Unreadable(_), Access::Unknown) => Access::Denied,
+                (_, access) => access,
+            };
+            segment.messages.push(msg);
+        }
+    }
+    segments
+}
+
+/// Remove and return the segment opened by the keyload
+pub fn take_segment(segments: &mut Vec<Segment>, keyload: &Address) -> Result<Segment> {
+    let index = segments
+        .iter()
+        .position(|segment| segment.keyload.as_ref() == Some(keyload))
+        .ok_or_else(|| anyhow!("No messages were retrieved for keyload {}", keyload))?;
+    Ok(segments.remove(index))
+}
+
+/// Check that the reader had the expected access to the segment opened by the keyload, and read the
+/// sent messages in it if access was granted
+pub fn verify_segment(segments: &mut Vec<Segment>, keyload: &Address, access: Access, sent_msgs: &[&str]) -> Result<()> {
+    let segment = take_segment(segments, keyload)?;
+    ensure!(
+        segment.access == access,
+        "Expected {:?} access to the messages of keyload {}, found {:?}",
+        access,
+        keyload,
+        segment.access
+    );
+    match access {
+        Access::Granted => verify_messages(sent_msgs, segment.messages),
+        Access::Denied | Access::Unknown => verify_unreadable(segment.messages),
+    }
+}
@@ -1,47 +1,35 @@
 use iota_streams::{
-    app::transport::tangle::client::Client,
     app_channels::api::{
         psk_from_seed,
         pskid_from_psk,
         tangle::{
-            Address, Author, Bytes, ChannelType, PublicKey, Subscriber, UnwrappedMessage,
+            Address, Author, Bytes, ChannelType, PublicKey, Subscriber, Transport, UnwrappedMessage,
         }
     },
-    core::{println, Result},
+    core::Result,
 };
 
-use crate::examples::{verify_messages, ALPH9};
-use rand::Rng;
+use crate::examples::{verify_messages, verify_unreadable, ExampleReport, SeedProvider};
 use core::str::FromStr;
 
-pub async fn example(node_url: &str) -> Result<()> {
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider) -> Result<ExampleReport> {
     // Generate a unique seed for the author
-    let seed: &str = &(0..81)
-        .map(|_| {
-            ALPH9
-                .chars()
-                .nth(rand::thread_rng().gen_range(0, 27))
-                .unwrap()
-        })
-        .collect::<String>();
-
-    // Create the Transport Client
-    let client = Client::new_from_url(node_url);
+    let seed: &str = &seeds.author_seed();
 
     // Generate an Author
-    let mut author = Author::new(seed, ChannelType::MultiBranch, client.clone());
+    let mut author = Author::new(seed, ChannelType::MultiBranch, transport.clone());
+
+    // Keep a report of everything the example sends and retrieves
+    let mut report = ExampleReport::new(ChannelType::MultiBranch);
 
     // Create the channel with an announcement message. Make sure to save the resulting link somewhere,
     let announcement_link = author.send_announce().await?;
     // This link acts as a root for the channel itself
     let ann_link_string = announcement_link.to_string();
-    println!(
-        "Announcement Link: {}\nTangle Index: {:#}\n",
-        ann_link_string, announcement_link.to_msg_index()
-    );
+    report.announcement(&announcement_link);
 
     // Generate a key to be used as a Pre Shared Key
-    let key = rand::thread_rng().gen::<[u8; 32]>();
+    let key = seeds.psk_key();
 
     // Author will now store a PSK to be used by Subscriber B. This will return a PskId (first half
     // of key for usage in keyload generation)
@@ -53,11 +41,11 @@ pub async fn example(node_url: &str) -> Result<()> {
     // In their own separate instances generate the subscriber(s) that will be attaching to the channel
 
     // This subscriber will subscribe traditionally
-    let mut subscriber_a = Subscriber::new("SubscriberA", client.clone());
+    let mut subscriber_a = Subscriber::new("SubscriberA", transport.clone());
     // This subscriber will use a PSK
-    let mut subscriber_b = Subscriber::new("SubscriberB", client.clone());
+    let mut subscriber_b = Subscriber::new("SubscriberB", transport.clone());
     // This subscriber will not subscribe at all
-    let mut subscriber_c = Subscriber::new("SubscriberC", client);
+    let mut subscriber_c = Subscriber::new("SubscriberC", transport);
 
     // Generate an Address object from the provided announcement link string from the Author
     let ann_address = Address::from_str(&ann_link_string)?;
@@ -81,11 +69,7 @@ pub async fn example(node_url: &str) -> Result<()> {
     // This is the subscription link that should be provided to the Author to complete subscription
     // for user A
     let sub_msg_a_str = subscribe_msg_a.to_string();
-
-    println!(
-        "Subscription msg:\n\tSubscriber A: {}\n\tTangle Index: {:#}\n",
-        sub_msg_a_str, subscribe_msg_a.to_msg_index()
-    );
+    report.subscription("Subscriber A", &subscribe_msg_a);
     // ----------------------------------------------------------------------
 
     // Get Address object from subscription message link provided by Subscriber A
@@ -101,27 +85,20 @@ pub async fn example(node_url: &str) -> Result<()> {
     // link itself, the second is an optional sequencing message.
     // ** In multi branch implementations, sequencing messages are sent to act as indexing references
     // for data location within the channel tree
-    let (keyload_a_link, _seq_a_link) = author.send_keyload(
+    let (keyload_a_link, seq_a_link) = author.send_keyload(
         &announcement_link,
         &vec![PublicKey::from_bytes(sub_a_pk)?.into()],
     ).await?;
-    println!(
-        "\nSent Keyload for Sub A: {}, tangle index: {:#}",
-        keyload_a_link,
-        keyload_a_link.to_msg_index()
-    );
+    report.keyload("for Sub A", &keyload_a_link, seq_a_link.as_ref());
 
     // Author will send the second Keyload with the PSK shared with Subscriber B (also linked to the
     // announcement message) to generate another new branch
-    let (keyload_b_link, _seq_b_link) = author.send_keyload(
+    let (keyload_b_link, seq_b_link) = author.send_keyload(
         &announcement_link,
         &vec![pskid.into()]
     ).await?;
-    println!(
-        "\nSent Keyload for Sub B: {}, tangle index: {:#}",
-        keyload_b_link,
-        keyload_b_link.to_msg_index()
-    );
+    report.keyload("for Sub B", &keyload_b_link, seq_b_link.as_ref());
+    report.phase("setup");
 
     // Author will now send signed encrypted messages to Sub A in a chain attached to Keyload A
     let msg_inputs_a = vec![
@@ -145,8 +122,7 @@ pub async fn example(node_url: &str) -> Result<()> {
             &Bytes::default(),
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
-        let seq_link = seq_link.unwrap();
-        println!("Sent msg for Sub A: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("for Sub A", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
 
@@ -167,12 +143,12 @@ pub async fn example(node_url: &str) -> Result<()> {
 
     let mut prev_msg_link = keyload_b_link;
     for input in &msg_inputs_b {
-        let (msg_link, _seq_link) = author.send_signed_packet(
+        let (msg_link, seq_link) = author.send_signed_packet(
             &prev_msg_link,
             &Bytes::default(),
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
-        println!("Sent msg for Sub B: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("for Sub B", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
 
@@ -183,38 +159,41 @@ pub async fn example(node_url: &str) -> Result<()> {
 
     let mut prev_msg_link = announcement_link;
     for input in &msg_inputs_all {
-        let (msg_link, _seq_link) = author.send_signed_packet(
+        let (msg_link, seq_link) = author.send_signed_packet(
             &prev_msg_link,
             &Bytes::default(),
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
-        println!("Sent msg for Anyone: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("for Anyone", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
 
+    report.phase("publish");
+
     // -----------------------------------------------------------------------------
     // Subscribers can now fetch these messages
     let mut retrieved = subscriber_a.fetch_all_next_msgs().await;
+    report.retrieved("SubscriberA", retrieved.len());
     let (retrieveda, retrievedb, retrieved_all) =
         split_retrieved(&mut retrieved, msg_inputs_a.len(), msg_inputs_b.len());
-    println!("\nVerifying message retrieval: SubscriberA");
     verify_messages(&msg_inputs_a, retrieveda)?;
-    verify_messages(&[], retrievedb)?;
+    verify_unreadable(retrievedb)?;
     verify_messages(&msg_inputs_all, retrieved_all)?;
 
     retrieved = subscriber_b.fetch_all_next_msgs().await;
+    report.retrieved("SubscriberB", retrieved.len());
     let (retrieveda, retrievedb, retrieved_all) =
         split_retrieved(&mut retrieved, msg_inputs_a.len(), msg_inputs_b.len());
-    println!("\nVerifying message retrieval: SubscriberB");
-    verify_messages(&[], retrieveda)?;
+    verify_unreadable(retrieveda)?;
     verify_messages(&msg_inputs_b, retrievedb)?;
     verify_messages(&msg_inputs_all, retrieved_all)?;
 
     retrieved = subscriber_c.fetch_all_next_msgs().await;
-    println!("\nVerifying message retrieval: SubscriberC");
+    report.retrieved("SubscriberC", retrieved.len());
     verify_messages(&msg_inputs_all, retrieved)?;
+    report.phase("fetch");
 
-    Ok(())
+    Ok(report)
 }
 
 fn split_retrieved(
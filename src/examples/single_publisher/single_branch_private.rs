@@ -1,43 +1,31 @@
 use iota_streams::{
-    app::transport::tangle::client::Client,
-    app_channels::api::tangle::{Address, Author, Bytes, ChannelType, Subscriber},
-    core::{println, Result},
+    app_channels::api::tangle::{Address, Author, Bytes, ChannelType, Subscriber, Transport},
+    core::Result,
 };
 
-use crate::examples::{verify_messages, ALPH9};
-use rand::Rng;
+use crate::examples::{verify_messages, ExampleReport, SeedProvider};
 use core::str::FromStr;
 
-pub async fn example(node_url: &str) -> Result<()> {
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider) -> Result<ExampleReport> {
     // Generate a unique seed for the author
-    let seed: &str = &(0..81)
-        .map(|_| {
-            ALPH9
-                .chars()
-                .nth(rand::thread_rng().gen_range(0, 27))
-                .unwrap()
-        })
-        .collect::<String>();
-
-    // Create the Transport Client
-    let client = Client::new_from_url(node_url);
+    let seed: &str = &seeds.author_seed();
 
     // Generate an Author
-    let mut author = Author::new(seed, ChannelType::SingleBranch, client.clone());
+    let mut author = Author::new(seed, ChannelType::SingleBranch, transport.clone());
+
+    // Keep a report of everything the example sends and retrieves
+    let mut report = ExampleReport::new(ChannelType::SingleBranch);
 
     // Create the channel with an announcement message. Make sure to save the resulting link somewhere,
     let announcement_link = author.send_announce().await?;
     // This link acts as a root for the channel itself
     let ann_link_string = announcement_link.to_string();
-    println!(
-        "Announcement Link: {}\nTangle Index: {:#}\n",
-        ann_link_string, announcement_link.to_msg_index()
-    );
+    report.announcement(&announcement_link);
 
     // ------------------------------------------------------------------
     // In their own separate instances generate the subscriber(s) that will be attaching to the channel
-    let mut subscriber_a = Subscriber::new("SubscriberA", client.clone());
-    let mut subscriber_b = Subscriber::new("SubscriberB", client);
+    let mut subscriber_a = Subscriber::new("SubscriberA", transport.clone());
+    let mut subscriber_b = Subscriber::new("SubscriberB", transport);
 
     // Generate an Address object from the provided announcement link string from the Author
     let ann_address = Address::from_str(&ann_link_string)?;
@@ -54,8 +42,8 @@ pub async fn example(node_url: &str) -> Result<()> {
     let sub_msg_a_str = subscribe_msg_a.to_string();
     let sub_msg_b_str = subscribe_msg_b.to_string();
 
-    println!("Subscription msgs:\n\tSubscriber A: {}\n\tTangle Index: {:#}\n\tSubscriber B: {}\n\tTangle Index: {:#}\n",
-        sub_msg_a_str, subscribe_msg_a.to_msg_index(), sub_msg_b_str, subscribe_msg_b.to_msg_index());
+    report.subscription("Subscriber A", &subscribe_msg_a);
+    report.subscription("Subscriber B", &subscribe_msg_b);
     // ----------------------------------------------------------------------
 
     // Get Address objects from subscription message links provided by expectant subscribers
@@ -72,7 +60,9 @@ pub async fn example(node_url: &str) -> Result<()> {
     // return a tuple containing the message links. The first is the message link itself, the second
     // is an optional sequencing message.
     // ** In single branch implementations, sequencing messages are not sent and can be ignored
-    let (keyload_link, _seq) = author.send_keyload_for_everyone(&announcement_link).await?;
+    let (keyload_link, seq) = author.send_keyload_for_everyone(&announcement_link).await?;
+    report.keyload("for everyone", &keyload_link, seq.as_ref());
+    report.phase("setup");
 
     // Author will now send signed encrypted messages in a chain
     let msg_inputs = vec![
@@ -81,22 +71,27 @@ pub async fn example(node_url: &str) -> Result<()> {
 
     let mut prev_msg_link = keyload_link;
     for input in &msg_inputs {
-        let (msg_link, _seq_link) = author.send_signed_packet(
+        let (msg_link, seq_link) = author.send_signed_packet(
             &prev_msg_link,
             &Bytes::default(),
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
-        println!("Sent msg: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("from Author", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
 
+    report.phase("publish");
+
     // -----------------------------------------------------------------------------
     // Subscribers can now fetch these messages
     let mut retrieved = subscriber_a.fetch_all_next_msgs().await;
+    report.retrieved("SubscriberA", retrieved.len());
     verify_messages(&msg_inputs, retrieved)?;
 
     retrieved = subscriber_b.fetch_all_next_msgs().await;
+    report.retrieved("SubscriberB", retrieved.len());
     verify_messages(&msg_inputs, retrieved)?;
+    report.phase("fetch");
 
-    Ok(())
+    Ok(report)
 }
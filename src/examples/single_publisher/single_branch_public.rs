@@ -1,38 +1,26 @@
 use iota_streams::{
-    app::transport::tangle::client::Client,
-    app_channels::api::tangle::{Address, Author, Bytes, ChannelType, Subscriber},
-    core::{println, Result},
+    app_channels::api::tangle::{Address, Author, Bytes, ChannelType, Subscriber, Transport},
+    core::Result,
 };
 
-use crate::examples::{verify_messages, ALPH9};
-use rand::Rng;
+use crate::examples::{verify_messages, ExampleReport, SeedProvider};
 use core::str::FromStr;
 
-pub async fn example(node_url: &str) -> Result<()> {
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider) -> Result<ExampleReport> {
     // Generate a unique seed for the author
-    let seed: &str = &(0..81)
-        .map(|_| {
-            ALPH9
-                .chars()
-                .nth(rand::thread_rng().gen_range(0, 27))
-                .unwrap()
-        })
-        .collect::<String>();
-
-    // Create the Transport Client
-    let client = Client::new_from_url(node_url);
+    let seed: &str = &seeds.author_seed();
 
     // Generate an Author
-    let mut author = Author::new(seed, ChannelType::SingleBranch, client.clone());
+    let mut author = Author::new(seed, ChannelType::SingleBranch, transport.clone());
+
+    // Keep a report of everything the example sends and retrieves
+    let mut report = ExampleReport::new(ChannelType::SingleBranch);
 
     // Create the channel with an announcement message. Make sure to save the resulting link somewhere,
     let announcement_link = author.send_announce().await?;
     // This link acts as a root for the channel itself
     let ann_link_string = announcement_link.to_string();
-    println!(
-        "Announcement Link: {}\nTangle Index: {:#}\n",
-        ann_link_string, announcement_link.to_msg_index()
-    );
+    report.announcement(&announcement_link);
 
     // Author will now send signed encrypted messages in a chain
     let msg_inputs = vec![
@@ -41,18 +29,19 @@ pub async fn example(node_url: &str) -> Result<()> {
 
     let mut prev_msg_link = announcement_link;
     for input in &msg_inputs {
-        let (msg_link, _seq_link) = author.send_signed_packet(
+        let (msg_link, seq_link) = author.send_signed_packet(
             &prev_msg_link,
             &Bytes::default(),
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
-        println!("Sent msg: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("from Author", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
+    report.phase("publish");
 
     // ------------------------------------------------------------------
     // In their own separate instances generate the subscriber(s) that will be attaching to the channel
-    let mut subscriber = Subscriber::new("SubscriberA", client);
+    let mut subscriber = Subscriber::new("SubscriberA", transport);
 
     // Generate an Address object from the provided announcement link string from the Author
     let ann_address = Address::from_str(&ann_link_string)?;
@@ -61,7 +50,9 @@ pub async fn example(node_url: &str) -> Result<()> {
     subscriber.receive_announcement(&ann_address).await?;
 
     let retrieved = subscriber.fetch_all_next_msgs().await;
+    report.retrieved("SubscriberA", retrieved.len());
     verify_messages(&msg_inputs, retrieved)?;
+    report.phase("fetch");
 
-    Ok(())
+    Ok(report)
 }
@@ -1,44 +1,31 @@
 use iota_streams::{
-    app::transport::tangle::client::Client,
-    app_channels::api::tangle::{Address, Author, Bytes, ChannelType, Subscriber},
-    core::{println, Result},
+    app_channels::api::tangle::{Address, Author, Bytes, ChannelType, Subscriber, Transport},
+    core::Result,
 };
 
-use crate::examples::{verify_messages, ALPH9};
-use rand::Rng;
+use crate::examples::{verify_messages, ExampleReport, SeedProvider};
 use core::str::FromStr;
-use iota_streams::app_channels::api::tangle::MessageContent;
 
-pub async fn example(node_url: &str) -> Result<()> {
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider) -> Result<ExampleReport> {
     // Generate a unique seed for the author
-    let seed: &str = &(0..81)
-        .map(|_| {
-            ALPH9
-                .chars()
-                .nth(rand::thread_rng().gen_range(0, 27))
-                .unwrap()
-        })
-        .collect::<String>();
-
-    // Create the Transport Client
-    let client = Client::new_from_url(node_url);
+    let seed: &str = &seeds.author_seed();
 
     // Generate an Author
-    let mut author = Author::new(seed, ChannelType::SingleDepth, client.clone());
+    let mut author = Author::new(seed, ChannelType::SingleDepth, transport.clone());
+
+    // Keep a report of everything the example sends and retrieves
+    let mut report = ExampleReport::new(ChannelType::SingleDepth);
 
     // Create the channel with an announcement message. Make sure to save the resulting link somewhere,
     let announcement_link = author.send_announce().await?;
     // This link acts as a root for the channel itself
     let ann_link_string = announcement_link.to_string();
-    println!(
-        "Announcement Link: {}\nTangle Index: {:#}\n",
-        ann_link_string, announcement_link.to_msg_index()
-    );
+    report.announcement(&announcement_link);
 
     // ------------------------------------------------------------------
     // In their own separate instances generate the subscriber(s) that will be attaching to the channel
-    let mut subscriber_a = Subscriber::new("SubscriberA", client.clone());
-    let mut subscriber_b = Subscriber::new("SubscriberB", client);
+    let mut subscriber_a = Subscriber::new("SubscriberA", transport.clone());
+    let mut subscriber_b = Subscriber::new("SubscriberB", transport);
 
     // Generate an Address object from the provided announcement link string from the Author
     let ann_address = Address::from_str(&ann_link_string)?;
@@ -57,31 +44,30 @@ pub async fn example(node_url: &str) -> Result<()> {
     // messages can be retrieved by sequence number
     let anchor_msg_link = announcement_link;
     for input in &msg_inputs {
-        let (msg_link, _seq_link) = author.send_signed_packet(
+        let (msg_link, seq_link) = author.send_signed_packet(
             &anchor_msg_link,
             &Bytes::default(),
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
-        println!("Sent msg: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("from Author", &msg_link, seq_link.as_ref());
     }
+    report.phase("publish");
 
     // -----------------------------------------------------------------------------
     // Subscribers can now fetch these messages
     let mut retrieved = subscriber_a.fetch_all_next_msgs().await;
+    report.retrieved("SubscriberA", retrieved.len());
     verify_messages(&msg_inputs, retrieved)?;
 
     retrieved = subscriber_b.fetch_all_next_msgs().await;
+    report.retrieved("SubscriberB", retrieved.len());
     verify_messages(&msg_inputs, retrieved)?;
 
     // A message can be retreived by its sequence number and anchor message
     let msg_3 = subscriber_b.receive_msg_by_sequence_number(&anchor_msg_link, 2).await?;
-    match msg_3.body {
-        MessageContent::SignedPacket { pk: _, public_payload: _, masked_payload } => {
-            assert_eq!(String::from_utf8(masked_payload.0)?, msg_inputs[2]);
-            println!("3rd Message sent matches the message retrieved");
-        }
-        _ => panic!("Not a signed packet")
-    }
+    verify_messages(&msg_inputs[2..3], vec![msg_3])?;
+    report.note("3rd Message sent matches the message retrieved");
+    report.phase("fetch");
 
-    Ok(())
+    Ok(report)
 }
@@ -1,48 +1,34 @@
 use iota_streams::{
-    app::transport::tangle::client::Client,
-    app_channels::api::tangle::{
-        Address, Author, Bytes, ChannelType, PublicKey, Subscriber, UnwrappedMessage,
-    },
-    core::{println, Result},
+    app_channels::api::tangle::{Address, Author, Bytes, ChannelType, PublicKey, Subscriber, Transport},
+    core::Result,
 };
 
-use crate::examples::{verify_messages, ALPH9};
-use rand::Rng;
+use crate::examples::{split_at_keyloads, verify_segment, Access, ExampleReport, SeedProvider};
 use core::str::FromStr;
 
-pub async fn example(node_url: &str) -> Result<()> {
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider) -> Result<ExampleReport> {
     // Generate a unique seed for the author
-    let seed: &str = &(0..81)
-        .map(|_| {
-            ALPH9
-                .chars()
-                .nth(rand::thread_rng().gen_range(0, 27))
-                .unwrap()
-        })
-        .collect::<String>();
-
-    // Create the Transport Client
-    let client = Client::new_from_url(node_url);
+    let seed: &str = &seeds.author_seed();
 
     // Generate an Author
-    let mut author = Author::new(seed, ChannelType::MultiBranch, client.clone());
+    let mut author = Author::new(seed, ChannelType::MultiBranch, transport.clone());
+
+    // Keep a report of everything the example sends and retrieves
+    let mut report = ExampleReport::new(ChannelType::MultiBranch);
 
     // Create the channel with an announcement message. Make sure to save the resulting link somewhere,
     let announcement_link = author.send_announce().await?;
     // This link acts as a root for the channel itself
     let ann_link_string = announcement_link.to_string();
-    println!(
-        "Announcement Link: {}\nTangle Index: {:#}\n",
-        ann_link_string, announcement_link.to_msg_index()
-    );
+    report.announcement(&announcement_link);
 
     // ------------------------------------------------------------------
     // In their own separate instances generate the subscriber(s) that will be attaching to the channel
 
     // This subscriber will subscribe traditionally
-    let mut subscriber_a = Subscriber::new("SubscriberA", client.clone());
+    let mut subscriber_a = Subscriber::new("SubscriberA", transport.clone());
     // This subscriber will be added later in the channel
-    let mut subscriber_b = Subscriber::new("SubscriberB", client);
+    let mut subscriber_b = Subscriber::new("SubscriberB", transport);
 
     // Generate an Address object from the provided announcement link string from the Author
     let ann_address = Address::from_str(&ann_link_string)?;
@@ -63,10 +49,8 @@ pub async fn example(node_url: &str) -> Result<()> {
     let sub_msg_a_str = subscribe_msg_a.to_string();
     let sub_msg_b_str = subscribe_msg_b.to_string();
 
-    println!(
-        "Subscription msgs:\n\tSubscriber A: {}\n\tTangle Index: {:#}\n\tSubscriber B: {}\n\tTangle Index: {:#}\n",
-        sub_msg_a_str, subscribe_msg_a.to_msg_index(), sub_msg_b_str, subscribe_msg_b.to_msg_index(),
-    );
+    report.subscription("Subscriber A", &subscribe_msg_a);
+    report.subscription("Subscriber B", &subscribe_msg_b);
     // ----------------------------------------------------------------------
 
     // Get Address object from subscription message link provided by Subscriber A
@@ -84,15 +68,11 @@ pub async fn example(node_url: &str) -> Result<()> {
     // Author sends keyload with the public key of Sub A (linked to announcement message) to generate
     // a new branch. This will return a tuple containing the message links. The first is the message
     // link itself, the second is an optional sequencing message.
-    let (_keyload_a_link, seq_a_link) = author.send_keyload(
+    let (keyload_a_link, seq_a_link) = author.send_keyload(
         &announcement_link,
         &vec![PublicKey::from_bytes(sub_a_pk)?.into()],
     ).await?;
-    println!(
-        "\nSent Keyload for Sub A: {}, sequence tangle index: {:#}\n",
-        _keyload_a_link,
-        seq_a_link.as_ref().unwrap().to_msg_index()
-    );
+    report.keyload("for Sub A", &keyload_a_link, seq_a_link.as_ref());
 
     // Author will now send signed encrypted messages to Sub A in a chain attached to Keyload A
     let msg_inputs_a = vec![
@@ -109,7 +89,7 @@ pub async fn example(node_url: &str) -> Result<()> {
         "A",
     ];
 
-    let mut prev_msg_link = _keyload_a_link;
+    let mut prev_msg_link = keyload_a_link.clone();
     let mut seq_msg_link = seq_a_link.unwrap();
     for input in &msg_inputs_a {
         let (msg_link, seq_link) = author.send_signed_packet(
@@ -118,7 +98,7 @@ pub async fn example(node_url: &str) -> Result<()> {
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
         let seq_link = seq_link.unwrap();
-        println!("Sent msg for Sub A: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("for Sub A", &msg_link, Some(&seq_link));
         prev_msg_link = msg_link;
         seq_msg_link = seq_link;
     }
@@ -129,16 +109,11 @@ pub async fn example(node_url: &str) -> Result<()> {
     // ** In order to allow users to access the message without having permission for the previous
     // messages, the keyload can be attached to the sequence message link, since the sequence message
     // link is stored in state regardless of user access to the referenced message.
-    let (_keyload_b_link, seq_b_link) = author.send_keyload(
+    let (keyload_b_link, seq_b_link) = author.send_keyload(
         &seq_msg_link,
             &vec![PublicKey::from_bytes(sub_b_pk)?.into()]
     ).await?;
-
-    println!(
-        "\nSent Keyload granting Sub B Forward Access, while revoking Sub A: {}, sequence tangle index: {:#}\n",
-        _keyload_b_link,
-        seq_b_link.as_ref().unwrap().to_msg_index()
-    );
+    report.keyload("granting Sub B, revoking Sub A", &keyload_b_link, seq_b_link.as_ref());
 
     // Author will now send signed encrypted messages to Sub B in a chain attached to Keyload B
     let msg_inputs_b = vec![
@@ -155,7 +130,7 @@ pub async fn example(node_url: &str) -> Result<()> {
         "B",
     ];
 
-    prev_msg_link = _keyload_b_link;
+    prev_msg_link = keyload_b_link.clone();
     seq_msg_link = seq_b_link.unwrap();
     for input in &msg_inputs_b {
         let (msg_link, seq_link) = author.send_signed_packet(
@@ -164,7 +139,7 @@ pub async fn example(node_url: &str) -> Result<()> {
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
         let seq_link = seq_link.unwrap();
-        println!("Sent msg for Sub B: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("for Sub B", &msg_link, Some(&seq_link));
         prev_msg_link = msg_link;
         seq_msg_link = seq_link;
     }
@@ -175,16 +150,11 @@ pub async fn example(node_url: &str) -> Result<()> {
     // ** In order to allow users to access the message without having permission for the previous
     // messages, the keyload can be attached to the sequence message link, since the sequence message
     // link is stored in state regardless of user access to the referenced message.
-    let (_keyload_c_link, seq_c_link) = author.send_keyload(
+    let (keyload_c_link, seq_c_link) = author.send_keyload(
         &seq_msg_link,
         &vec![PublicKey::from_bytes(sub_a_pk)?.into()]
     ).await?;
-
-    println!(
-        "\nSent Keyload granting Sub A Forward Access again, while revoking Sub B: {}, sequence tangle index: {:#}\n",
-        _keyload_c_link,
-        seq_c_link.as_ref().unwrap().to_msg_index()
-    );
+    report.keyload("granting Sub A again, revoking Sub B", &keyload_c_link, seq_c_link.as_ref());
 
     // Author will send signed encrypted messages to Sub A again in a chain attached to Keyload C
     let msg_inputs_c = vec![
@@ -202,84 +172,36 @@ pub async fn example(node_url: &str) -> Result<()> {
         "Again",
     ];
 
-    prev_msg_link = _keyload_c_link;
+    prev_msg_link = keyload_c_link.clone();
     for input in &msg_inputs_c {
         let (msg_link, seq_link) = author.send_signed_packet(
             &prev_msg_link,
             &Bytes::default(),
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
-        let seq_link = seq_link.unwrap();
-        println!("Sent msg for Sub A again: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("for Sub A again", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
 
-    // -----------------------------------------------------------------------------
-    // Subscribers can now fetch these messages
-    let mut retrieved = subscriber_a.fetch_all_next_msgs().await;
-    let (retrieveda, retrievedb, retrieveda2) = split_retrieved(
-        &mut retrieved,
-        msg_inputs_a.len(),
-        msg_inputs_b.len(),
-        msg_inputs_c.len(),
-    );
-    println!("\nVerifying message retrieval: SubscriberA");
-    verify_messages(&msg_inputs_a, retrieveda)?;
-    verify_messages(&[], retrievedb)?;
-    verify_messages(&msg_inputs_c, retrieveda2)?;
-
-    retrieved = subscriber_b.fetch_all_next_msgs().await;
-    let (retrieveda, retrievedb, retrieveda2) = split_retrieved(
-        &mut retrieved,
-        msg_inputs_a.len(),
-        msg_inputs_b.len(),
-        msg_inputs_c.len(),
-    );
-    println!("\nVerifying message retrieval: SubscriberB");
-    verify_messages(&[], retrieveda)?;
-    verify_messages(&msg_inputs_b, retrievedb)?;
-    verify_messages(&[], retrieveda2)?;
-
-    Ok(())
-}
-
-fn split_retrieved(
-    retrieved: &mut Vec<UnwrappedMessage>,
-    len1: usize,
-    len2: usize,
-    len3: usize,
-) -> (
-    Vec<UnwrappedMessage>,
-    Vec<UnwrappedMessage>,
-    Vec<UnwrappedMessage>,
-) {
-    let mut retrieved_msgs_a = Vec::new();
-    let mut retrieved_msgs_b = Vec::new();
-    let mut retrieved_msgs_a2 = Vec::new();
-
-    // Keyload A
-    retrieved.remove(0);
-
-    for _ in 0..len1 {
-        // Messages for sub A
-        retrieved_msgs_a.push(retrieved.remove(0));
-    }
-
-    // Keyload B
-    retrieved.remove(0);
-
-    for _ in 0..len2 {
-        // Messages for sub B
-        retrieved_msgs_b.push(retrieved.remove(0));
-    }
-
-    // Keyload C
-    retrieved.remove(0);
+    report.phase("publish");
 
-    for _ in 0..len3 {
-        // Messages for sub A again
-        retrieved_msgs_a2.push(retrieved.remove(0));
-    }
-
-    (retrieved_msgs_a, retrieved_msgs_b, retrieved_msgs_a2)
+    // -----------------------------------------------------------------------------
+    // Subscribers can now fetch these messages. Readers are handed every keyload, so the retrieved
+    // messages can be split at each keyload to check which branches the reader had access to
+    let retrieved = subscriber_a.fetch_all_next_msgs().await;
+    report.retrieved("SubscriberA", retrieved.len());
+    let mut segments = split_at_keyloads(retrieved);
+    verify_segment(&mut segments, &keyload_a_link, Access::Granted, &msg_inputs_a)?;
+    verify_segment(&mut segments, &keyload_b_link, Access::Denied, &[])?;
+    verify_segment(&mut segments, &keyload_c_link, Access::Granted, &msg_inputs_c)?;
+
+    let retrieved = subscriber_b.fetch_all_next_msgs().await;
+    report.retrieved("SubscriberB", retrieved.len());
+    let mut segments = split_at_keyloads(retrieved);
+    verify_segment(&mut segments, &keyload_a_link, Access::Denied, &[])?;
+    verify_segment(&mut segments, &keyload_b_link, Access::Granted, &msg_inputs_b)?;
+    verify_segment(&mut segments, &keyload_c_link, Access::Denied, &[])?;
+    report.phase("fetch");
+
+    Ok(report)
 }
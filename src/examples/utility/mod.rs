@@ -1,6 +1,8 @@
 pub mod fetch_prev;
 pub mod grant_and_revoke_access;
+pub mod network_faults;
 pub mod pk_keyloads;
 pub mod psk_keyloads;
 pub mod state_recovery;
 pub mod stateless_recovery;
+pub mod tagged_packets;
@@ -0,0 +1,144 @@
+use iota_streams::{
+    app_channels::api::tangle::{Address, Author, Bytes, ChannelType, PublicKey, Subscriber, Transport},
+    core::Result,
+};
+
+use crate::{
+    examples::{group_by_publisher, ExampleReport, SeedProvider},
+    transports::faulty::{Fault, FaultyTransport},
+};
+
+/**
+ * This example shows how sequencing and retrieval behave when the network misbehaves. Two
+ * Subscribers publish in alternating order in a single branch, syncing their state before each
+ * publish, while one of the messages of Subscriber A is dropped, delayed, duplicated or reordered
+ * on its way to the transport. The Author then fetches the branch before and after the network has
+ * caught up with any held back messages, and checks that only the sequence of Subscriber A is held
+ * up, for good when its message is lost.
+ */
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider) -> Result<ExampleReport> {
+    let mut report = ExampleReport::new(ChannelType::MultiBranch);
+
+    let faults = vec![
+        None,
+        Some(Fault::Drop),
+        Some(Fault::Delay(3)),
+        Some(Fault::Duplicate),
+        Some(Fault::Reorder),
+    ];
+
+    for fault in faults {
+        report.note(format!("Fault applied to the 2nd message of Subscriber A: {:?}", fault));
+        scenario(FaultyTransport::scripted(transport.clone()), fault, seeds, &mut report).await?;
+        report.phase(&format!("{:?}", fault));
+    }
+
+    Ok(report)
+}
+
+async fn scenario<T: Transport>(
+    mut transport: FaultyTransport<T>,
+    fault: Option<Fault>,
+    seeds: &mut SeedProvider,
+    report: &mut ExampleReport,
+) -> Result<()> {
+    // Generate a unique seed for the author
+    let seed: &str = &seeds.author_seed();
+
+    // Generate an Author and the two publishing Subscribers, and give both access to a single branch
+    let mut author = Author::new(seed, ChannelType::MultiBranch, transport.clone());
+    let announcement_link = author.send_announce().await?;
+
+    let mut subscriber_a = Subscriber::new("SubscriberA", transport.clone());
+    let mut subscriber_b = Subscriber::new("SubscriberB", transport.clone());
+    subscriber_a.receive_announcement(&announcement_link).await?;
+    subscriber_b.receive_announcement(&announcement_link).await?;
+
+    let sub_a_pk = PublicKey::from_bytes(subscriber_a.get_public_key().as_bytes())?;
+    let sub_b_pk = PublicKey::from_bytes(subscriber_b.get_public_key().as_bytes())?;
+
+    let subscribe_msg_a = subscriber_a.send_subscribe(&announcement_link).await?;
+    let subscribe_msg_b = subscriber_b.send_subscribe(&announcement_link).await?;
+    author.receive_subscribe(&subscribe_msg_a).await?;
+    author.receive_subscribe(&subscribe_msg_b).await?;
+
+    let (keyload_link, _seq_link) = author.send_keyload_for_everyone(&announcement_link).await?;
+
+    // Subscribers A and B send messages in an alternating chain attached to the keyload
+    let msg_inputs = vec!["First", "Second", "Third", "Fourth"];
+
+    let mut prev_msg_link = keyload_link;
+    for (i, input) in msg_inputs.iter().enumerate() {
+        if i == 1 {
+            transport.inject_next(fault);
+        }
+        prev_msg_link = publish(&mut subscriber_a, "from Sub A", &prev_msg_link, input, report).await;
+        prev_msg_link = publish(&mut subscriber_b, "from Sub B", &prev_msg_link, input, report).await;
+    }
+
+    for applied in transport.applied() {
+        report.note(format!("Applied {:?} to msg: {}", applied.fault, applied.link));
+    }
+
+    // Author fetches the branch, then again once every held back message has been delivered
+    let publishers = [sub_a_pk, sub_b_pk];
+    let groups = group_by_publisher(author.fetch_all_next_msgs().await, &publishers);
+    let (from_a, from_b) = (groups.get(&publishers[0]).len(), groups.get(&publishers[1]).len());
+    report.retrieved(&format!("Author from Sub A ({:?})", fault), from_a);
+    report.retrieved(&format!("Author from Sub B ({:?})", fault), from_b);
+
+    transport.flush().await?;
+    let groups = group_by_publisher(author.fetch_all_next_msgs().await, &publishers);
+    let (late_a, late_b) = (groups.get(&publishers[0]).len(), groups.get(&publishers[1]).len());
+    report.retrieved(&format!("Author from Sub A after catching up ({:?})", fault), late_a);
+    report.retrieved(&format!("Author from Sub B after catching up ({:?})", fault), late_b);
+
+    // Each publisher has its own sequence, so Subscriber B is never held up by the messages of A
+    anyhow::ensure!(
+        from_b + late_b == msg_inputs.len(),
+        "Author retrieved {} of the {} messages of Sub B under {:?}",
+        from_b + late_b,
+        msg_inputs.len(),
+        fault
+    );
+    // A message that is held back is retrieved once delivered, a lost one stalls the sequence of A.
+    // Readers refuse a link holding more than one message, so a duplicated message is lost as well
+    let complete = match fault {
+        None | Some(Fault::Delay(_)) | Some(Fault::Reorder) => true,
+        Some(Fault::Drop) | Some(Fault::Duplicate) => false,
+    };
+    anyhow::ensure!(
+        (from_a + late_a == msg_inputs.len()) == complete,
+        "Author retrieved {} of the {} messages of Sub A under {:?}",
+        from_a + late_a,
+        msg_inputs.len(),
+        fault
+    );
+
+    Ok(())
+}
+
+// Sync and publish a message, reporting rather than returning any error, as errors are an expected
+// outcome under some faults. Returns the link the next message should be attached to.
+async fn publish<T: Transport>(
+    subscriber: &mut Subscriber<T>,
+    label: &str,
+    prev_msg_link: &Address,
+    input: &str,
+    report: &mut ExampleReport,
+) -> Address {
+    subscriber.sync_state().await;
+    match subscriber
+        .send_signed_packet(prev_msg_link, &Bytes::default(), &Bytes(input.as_bytes().to_vec()))
+        .await
+    {
+        Ok((msg_link, seq_link)) => {
+            report.message(label, &msg_link, seq_link.as_ref());
+            msg_link
+        }
+        Err(e) => {
+            report.note(format!("Failed to send msg: {}", e));
+            prev_msg_link.clone()
+        }
+    }
+}
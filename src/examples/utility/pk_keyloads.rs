@@ -1,44 +1,32 @@
 use iota_streams::{
-    app::transport::tangle::client::Client,
-    app_channels::api::tangle::{Address, Author, ChannelType, Subscriber},
-    core::{println, Result},
+    app_channels::api::tangle::{Address, Author, ChannelType, Subscriber, Transport},
+    core::Result,
 };
 
-use crate::examples::ALPH9;
+use crate::examples::{ExampleReport, SeedProvider};
 use iota_streams::app_channels::api::tangle::PublicKey;
-use rand::Rng;
 use core::str::FromStr;
 
-pub async fn example(node_url: &str) -> Result<()> {
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider) -> Result<ExampleReport> {
     // Generate a unique seed for the author
-    let seed: &str = &(0..81)
-        .map(|_| {
-            ALPH9
-                .chars()
-                .nth(rand::thread_rng().gen_range(0, 27))
-                .unwrap()
-        })
-        .collect::<String>();
-
-    // Create the Transport Client
-    let client = Client::new_from_url(node_url);
+    let seed: &str = &seeds.author_seed();
 
     // Generate an Author
-    let mut author = Author::new(seed, ChannelType::MultiBranch, client.clone());
+    let mut author = Author::new(seed, ChannelType::MultiBranch, transport.clone());
+
+    // Keep a report of everything the example sends and retrieves
+    let mut report = ExampleReport::new(ChannelType::MultiBranch);
 
     // Create the channel with an announcement message. Make sure to save the resulting link somewhere,
     let announcement_link = author.send_announce().await?;
     // This link acts as a root for the channel itself
     let ann_link_string = announcement_link.to_string();
-    println!(
-        "Announcement Link: {}\nTangle Index: {:#}\n",
-        ann_link_string, announcement_link.to_msg_index()
-    );
+    report.announcement(&announcement_link);
 
     // ------------------------------------------------------------------
     // In their own separate instances generate the subscriber(s) that will be attaching to the channel
-    let mut subscriber_a = Subscriber::new("SubscriberA", client.clone());
-    let mut subscriber_b = Subscriber::new("SubscriberB", client);
+    let mut subscriber_a = Subscriber::new("SubscriberA", transport.clone());
+    let mut subscriber_b = Subscriber::new("SubscriberB", transport);
 
     // Generate an Address object from the provided announcement link string from the Author
     let ann_address = Address::from_str(&ann_link_string)?;
@@ -55,9 +43,8 @@ pub async fn example(node_url: &str) -> Result<()> {
     let sub_msg_a_str = subscribe_msg_a.to_string();
     let sub_msg_b_str = subscribe_msg_b.to_string();
 
-    println!("Subscription msgs:\n\tSubscriber A: {}\n\tTangle Index: {:#}\n\tSubscriber B: {}\n\tTangle Index: {:#}\n",
-             sub_msg_a_str, subscribe_msg_a.to_msg_index(), sub_msg_b_str, subscribe_msg_b.to_msg_index()
-    );
+    report.subscription("Subscriber A", &subscribe_msg_a);
+    report.subscription("Subscriber B", &subscribe_msg_b);
 
     // These are the public keys that the Author will use to specify specific users in a keyload
     let sub_a_pk = subscriber_a.get_public_key().as_bytes();
@@ -73,33 +60,26 @@ pub async fn example(node_url: &str) -> Result<()> {
     author.receive_subscribe(&sub_b_address).await?;
 
     // Expectant users are now subscribed and can be included in a Keyload message
+    report.phase("setup");
 
     // Author sends Keyload for all subscribers
-    let (keyload_all_link, _seq) = author.send_keyload_for_everyone(&announcement_link).await?;
-    println!(
-        "Keyload link for All: {}\n\tTangle Index: {:#}\n",
-        keyload_all_link, keyload_all_link.to_msg_index()
-    );
+    let (keyload_all_link, seq) = author.send_keyload_for_everyone(&announcement_link).await?;
+    report.keyload("for All", &keyload_all_link, seq.as_ref());
 
     // Author sends Keyload for just Subscriber A
-    let (keyload_a_link, _seq) = author.send_keyload(
+    let (keyload_a_link, seq) = author.send_keyload(
         &announcement_link,
         &vec![PublicKey::from_bytes(sub_a_pk)?.into()],
     ).await?;
-    println!(
-        "Keyload link for Subscriber A: {}\n\tTangle Index: {:#}\n",
-        keyload_a_link, keyload_a_link.to_msg_index()
-    );
+    report.keyload("for Subscriber A", &keyload_a_link, seq.as_ref());
 
     // Author sends Keyload for just Subscriber B
-    let (keyload_b_link, _seq) = author.send_keyload(
+    let (keyload_b_link, seq) = author.send_keyload(
         &announcement_link,
         &vec![PublicKey::from_bytes(sub_b_pk)?.into()],
     ).await?;
-    println!(
-        "Keyload link for Subscriber B: {}\n\tTangle Index: {:#}\n",
-        keyload_b_link, keyload_b_link.to_msg_index()
-    );
+    report.keyload("for Subscriber B", &keyload_b_link, seq.as_ref());
+    report.phase("publish");
 
-    Ok(())
+    Ok(report)
 }
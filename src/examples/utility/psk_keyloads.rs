@@ -1,49 +1,37 @@
 use iota_streams::{
-    app::transport::tangle::client::Client,
     app_channels::api::{
         psk_from_seed,
         pskid_from_psk,
-        tangle::{Address, Author, ChannelType, Subscriber}
+        tangle::{Address, Author, ChannelType, Subscriber, Transport}
     },
-    core::{println, Result},
+    core::Result,
 };
 
-use crate::examples::ALPH9;
-use rand::Rng;
+use crate::examples::{ExampleReport, SeedProvider};
 use core::str::FromStr;
 
 /**
  * This example demonstrates how to use a Pre-Shared Key for accessing a branch. PSK's should only
  * be used for read access at this time.
  */
-pub async fn example(node_url: &str) -> Result<()> {
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider) -> Result<ExampleReport> {
     // Generate a unique seed for the author
-    let seed: &str = &(0..81)
-        .map(|_| {
-            ALPH9
-                .chars()
-                .nth(rand::thread_rng().gen_range(0, 27))
-                .unwrap()
-        })
-        .collect::<String>();
-
-    // Create the Transport Client
-    let client = Client::new_from_url(node_url);
+    let seed: &str = &seeds.author_seed();
 
     // Generate an Author
-    let mut author = Author::new(seed, ChannelType::MultiBranch, client.clone());
+    let mut author = Author::new(seed, ChannelType::MultiBranch, transport.clone());
+
+    // Keep a report of everything the example sends and retrieves
+    let mut report = ExampleReport::new(ChannelType::MultiBranch);
 
     // Create the channel with an announcement message. Make sure to save the resulting link somewhere,
     let announcement_link = author.send_announce().await?;
     // This link acts as a root for the channel itself
     let ann_link_string = announcement_link.to_string();
-    println!(
-        "Announcement Link: {}\nTangle Index: {:#}\n",
-        ann_link_string, announcement_link.to_msg_index()
-    );
+    report.announcement(&announcement_link);
 
     // Generate a key to be used as a Pre Shared Key
-    let key = rand::thread_rng().gen::<[u8; 32]>();
+    let key = seeds.psk_key();
 
     // Author will now store a PSK to be used by Subscriber B. This will return a PskId (first half
     // of key for usage in keyload generation)
@@ -53,7 +41,7 @@ pub async fn example(node_url: &str) -> Result<()> {
 
     // ------------------------------------------------------------------
     // In their own separate instances generate the subscriber(s) that will be attaching to the channel
-    let mut subscriber = Subscriber::new("SubscriberA", client);
+    let mut subscriber = Subscriber::new("SubscriberA", transport);
 
     // Generate an Address object from the provided announcement link string from the Author
     let ann_address = Address::from_str(&ann_link_string)?;
@@ -64,13 +52,12 @@ pub async fn example(node_url: &str) -> Result<()> {
     // Store the PSK in the Subscriber instance
     let _sub_pskid = subscriber.store_psk(pskid, psk);
     // ----------------------------------------------------------------------
+    report.phase("setup");
 
     // Author sends Keyload with PSK included
-    let (keyload_all_link, _seq) = author.send_keyload(&announcement_link, &vec![pskid.into()]).await?;
-    println!(
-        "Keyload link for All: {}\n\tTangle Index: {:#}\n",
-        keyload_all_link, keyload_all_link
-    );
+    let (keyload_all_link, seq) = author.send_keyload(&announcement_link, &vec![pskid.into()]).await?;
+    report.keyload("for All", &keyload_all_link, seq.as_ref());
+    report.phase("publish");
 
-    Ok(())
+    Ok(report)
 }
@@ -1,69 +1,101 @@
 use iota_streams::{
-    app::transport::tangle::client::Client,
-    app_channels::api::tangle::{Author, Bytes, ChannelType},
-    core::{println, Result},
+    app_channels::api::tangle::{Author, Bytes, ChannelType, Transport},
+    core::Result,
 };
 
-use crate::examples::{verify_messages, ALPH9};
-use rand::Rng;
+use crate::{
+    examples::{verify_messages, ExampleReport, SeedProvider},
+    state::{reencrypt, Password, PasswordSource},
+};
+use std::path::Path;
+use zeroize::Zeroize;
 
-pub async fn example(node_url: &str) -> Result<()> {
+/**
+ * The exported state is written to a file in `dir`. Each run should be given its own directory so
+ * that concurrent runs do not overwrite each other's state.
+ *
+ * The state is encrypted with the password in `STREAMS_STATE_PASSWORD`, or one drawn from `seeds` if
+ * it is not set, so that the example runs unattended and deterministic runs stay reproducible. A
+ * password drawn from seeds is only fit for an example: applications should read it from the
+ * environment, a key file or a prompt, see `PasswordSource`.
+ */
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider, dir: &Path) -> Result<ExampleReport> {
     // Generate a unique seed for the author
-    let seed: &str = &(0..81)
-        .map(|_| {
-            ALPH9
-                .chars()
-                .nth(rand::thread_rng().gen_range(0, 27))
-                .unwrap()
-        })
-        .collect::<String>();
-
-    // Create the Transport Client
-    let client = Client::new_from_url(node_url);
+    let seed: &str = &seeds.author_seed();
 
     // Generate an Author
-    let mut author = Author::new(seed, ChannelType::SingleBranch, client.clone());
+    let mut author = Author::new(seed, ChannelType::SingleBranch, transport.clone());
+
+    // Keep a report of everything the example sends and retrieves
+    let mut report = ExampleReport::new(ChannelType::SingleBranch);
 
     // Create the channel with an announcement message. Make sure to save the resulting link somewhere,
     let announcement_link = author.send_announce().await?;
-    println!(
-        "Announcement Link: {}\nTangle Index: {:#}\n",
-        announcement_link, announcement_link
-    );
+    report.announcement(&announcement_link);
 
     // Author will now send signed encrypted messages in a chain
     let msg_inputs = vec!["Send", "Some", "Messages"];
 
     let mut prev_msg_link = announcement_link;
     for input in &msg_inputs {
-        let (msg_link, _seq_link) = author.send_signed_packet(
+        let (msg_link, seq_link) = author.send_signed_packet(
             &prev_msg_link,
             &Bytes::default(),
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
-        println!("Sent msg: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("from Author", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
 
+    report.phase("publish");
+
+    // Source the export password. Passwords are zeroized once dropped
+    let password = match PasswordSource::env().load() {
+        Ok(password) => password,
+        Err(_) => generated_password(seeds),
+    };
+
     // Export State of author
-    let state = author.export("Password").await?;
+    let state = author.export(password.expose()).await?;
     // Write state to file
-    std::fs::write("./author_state.bin", state)?;
+    let state_file = dir.join("author_state.bin");
+    std::fs::write(&state_file, state)?;
+
+    // Passwords can be rotated without starting over: the stored state is encrypted again under a
+    // new password
+    let new_password = generated_password(seeds);
+    let state = reencrypt::<Author<T>>(&std::fs::read(&state_file)?, &password, &new_password, transport.clone()).await?;
+    std::fs::write(&state_file, state)?;
+    drop(password);
+    report.note("Exported state re-encrypted under a new password");
 
     // Retrieve State from file
-    let state = std::fs::read("./author_state.bin")?;
+    let state = std::fs::read(&state_file)?;
 
     // Import state
-    let mut new_author = Author::import(&state, "Password", client).await?;
+    let mut new_author = Author::import(&state, new_password.expose(), transport).await?;
+
+    report.phase("recover");
 
-    let (last_msg_link, _seq) = new_author.send_signed_packet(
+    let (last_msg_link, seq) = new_author.send_signed_packet(
         &prev_msg_link,
         &Bytes::default(),
         &Bytes("One last message".as_bytes().to_vec()),
     ).await?;
+    report.message("from imported Author", &last_msg_link, seq.as_ref());
 
     let retrieved = new_author.fetch_prev_msgs(&last_msg_link, msg_inputs.len()).await?;
+    report.retrieved("Imported Author", retrieved.len());
     verify_messages(&msg_inputs, retrieved)?;
+    report.phase("fetch");
+
+    Ok(report)
+}
 
-    Ok(())
+// A password drawn from the seed provider, reproducible in deterministic runs
+fn generated_password(seeds: &mut SeedProvider) -> Password {
+    let mut key = seeds.psk_key();
+    let password = Password::new(hex::encode(key));
+    key.zeroize();
+    password
 }
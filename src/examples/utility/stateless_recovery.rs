@@ -1,67 +1,62 @@
 use iota_streams::{
-    app::transport::tangle::client::Client,
-    app_channels::api::tangle::{Author, Bytes, ChannelType},
-    core::{println, Result},
+    app_channels::api::tangle::{Author, Bytes, ChannelType, Transport},
+    core::Result,
 };
 
-use crate::examples::{verify_messages, ALPH9};
-use rand::Rng;
+use crate::examples::{verify_messages, ExampleReport, SeedProvider};
 
-pub async fn example(node_url: &str) -> Result<()> {
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider) -> Result<ExampleReport> {
     // Generate a unique seed for the author
-    let seed: &str = &(0..81)
-        .map(|_| {
-            ALPH9
-                .chars()
-                .nth(rand::thread_rng().gen_range(0, 27))
-                .unwrap()
-        })
-        .collect::<String>();
-
-    // Create the Transport Client
-    let client = Client::new_from_url(node_url);
+    let seed: &str = &seeds.author_seed();
 
     // Generate an Author
-    let mut author = Author::new(seed, ChannelType::SingleBranch, client.clone());
+    let mut author = Author::new(seed, ChannelType::SingleBranch, transport.clone());
+
+    // Keep a report of everything the example sends and retrieves
+    let mut report = ExampleReport::new(ChannelType::SingleBranch);
 
     // Create the channel with an announcement message. Make sure to save the resulting link somewhere,
     let announcement_link = author.send_announce().await?;
-    println!(
-        "Announcement Link: {}\nTangle Index: {:#}\n",
-        announcement_link, announcement_link.to_msg_index()
-    );
+    report.announcement(&announcement_link);
 
     // Author will now send signed encrypted messages in a chain
     let msg_inputs = vec!["Send", "Some", "Messages"];
 
     let mut prev_msg_link = announcement_link.clone();
     for input in &msg_inputs {
-        let (msg_link, _seq_link) = author.send_signed_packet(
+        let (msg_link, seq_link) = author.send_signed_packet(
             &prev_msg_link,
             &Bytes::default(),
             &Bytes(input.as_bytes().to_vec()),
         ).await?;
-        println!("Sent msg: {}, tangle index: {:#}", msg_link, msg_link.to_msg_index());
+        report.message("from Author", &msg_link, seq_link.as_ref());
         prev_msg_link = msg_link;
     }
 
+    report.phase("publish");
+
     // Recover from scratch
     let mut new_author =
         Author::recover(
             seed,
             &announcement_link,
             ChannelType::SingleBranch,
-            client
+            transport
         ).await?;
 
-    let (last_msg_link, _seq) = new_author.send_signed_packet(
+    report.phase("recover");
+
+    let (last_msg_link, seq) = new_author.send_signed_packet(
         &prev_msg_link,
         &Bytes::default(),
         &Bytes("One last message".as_bytes().to_vec()),
     ).await?;
+    report.message("from recovered Author", &last_msg_link, seq.as_ref());
 
     let retrieved = new_author.fetch_prev_msgs(&last_msg_link, msg_inputs.len()).await?;
+    report.retrieved("Recovered Author", retrieved.len());
     verify_messages(&msg_inputs, retrieved)?;
+    report.phase("fetch");
 
-    Ok(())
+    Ok(report)
 }
@@ -0,0 +1,139 @@
+use iota_streams::{
+    app_channels::api::tangle::{Author, Bytes, ChannelType, PublicKey, Subscriber, Transport},
+    core::Result,
+};
+
+use crate::examples::{
+    group_by_publisher, split_at_keyloads, verify_segment, verify_signed, verify_tagged, Access, ExampleReport,
+    SeedProvider, Segment,
+};
+
+pub async fn example<T: Transport>(transport: T, seeds: &mut SeedProvider) -> Result<ExampleReport> {
+    // Generate a unique seed for the author
+    let seed: &str = &seeds.author_seed();
+
+    // Generate an Author
+    let mut author = Author::new(seed, ChannelType::MultiBranch, transport.clone());
+
+    // Keep a report of everything the example sends and retrieves
+    let mut report = ExampleReport::new(ChannelType::MultiBranch);
+
+    // Create the channel with an announcement message. Make sure to save the resulting link somewhere,
+    // This link acts as a root for the channel itself
+    let announcement_link = author.send_announce().await?;
+    report.announcement(&announcement_link);
+
+    // ------------------------------------------------------------------
+    // In their own separate instances generate the subscriber(s) that will be attaching to the channel
+
+    // This subscriber will subscribe traditionally
+    let mut subscriber_a = Subscriber::new("SubscriberA", transport.clone());
+    // This subscriber will not subscribe at all
+    let mut subscriber_b = Subscriber::new("SubscriberB", transport);
+
+    // Receive the announcement message to start listening to the channel
+    subscriber_a.receive_announcement(&announcement_link).await?;
+    subscriber_b.receive_announcement(&announcement_link).await?;
+
+    // Sub A sends subscription message linked to announcement message, and the Author processes it
+    let subscribe_msg_a = subscriber_a.send_subscribe(&announcement_link).await?;
+    report.subscription("Subscriber A", &subscribe_msg_a);
+    author.receive_subscribe(&subscribe_msg_a).await?;
+
+    let sub_a_pk = PublicKey::from_bytes(subscriber_a.get_public_key().as_bytes())?;
+    // Readers use the public key of the Author to tell which packets it signed
+    let author_pk = PublicKey::from_bytes(author.get_public_key().as_bytes())?;
+    report.phase("setup");
+
+    // Signed packets carry the public key of their publisher and a signature over the message. Tagged
+    // packets carry neither, only a MAC derived from the branch key: they are cheaper to send and
+    // smaller, and readers know they were sent by someone with access to the branch, but not by whom.
+    // Both have a public payload readable by anyone with access to the branch, and a masked payload.
+
+    // The Author starts with tagged and signed packets in a public chain attached to the announcement
+    let msg_inputs_public_tagged = vec!["Tagged", "Packets", "Readable", "By", "Anyone"];
+    let msg_inputs_public_signed = vec!["Signed", "Packets", "Readable", "By", "Anyone"];
+
+    let mut prev_msg_link = announcement_link.clone();
+    for input in &msg_inputs_public_tagged {
+        let (msg_link, seq_link) = author
+            .send_tagged_packet(&prev_msg_link, &Bytes::default(), &Bytes(input.as_bytes().to_vec()))
+            .await?;
+        report.message("tagged for Anyone", &msg_link, seq_link.as_ref());
+        prev_msg_link = msg_link;
+    }
+    for input in &msg_inputs_public_signed {
+        let (msg_link, seq_link) = author
+            .send_signed_packet(&prev_msg_link, &Bytes::default(), &Bytes(input.as_bytes().to_vec()))
+            .await?;
+        report.message("signed for Anyone", &msg_link, seq_link.as_ref());
+        prev_msg_link = msg_link;
+    }
+
+    // Author sends a Keyload for Subscriber A to start a private branch, and signed packets in it
+    let (keyload_a_link, seq_link) = author.send_keyload(&announcement_link, &vec![sub_a_pk.into()]).await?;
+    report.keyload("for Sub A", &keyload_a_link, seq_link.as_ref());
+
+    let msg_inputs_private_signed = vec!["Signed", "Packets", "Readable", "By", "Subscriber", "A"];
+    let mut prev_msg_link = keyload_a_link.clone();
+    for input in &msg_inputs_private_signed {
+        let (msg_link, seq_link) = author
+            .send_signed_packet(&prev_msg_link, &Bytes::default(), &Bytes(input.as_bytes().to_vec()))
+            .await?;
+        report.message("signed for Sub A", &msg_link, seq_link.as_ref());
+        prev_msg_link = msg_link;
+    }
+    report.phase("publish");
+
+    // -----------------------------------------------------------------------------
+    // Subscriber A reads both chains. Signed packets are grouped under the Author that signed them,
+    // tagged packets cannot be attributed to anyone
+    let retrieved = subscriber_a.fetch_all_next_msgs().await;
+    report.retrieved("SubscriberA", retrieved.len());
+    let mut segments = split_at_keyloads(retrieved);
+    verify_public_chain(&mut segments, &author_pk, &msg_inputs_public_tagged, &msg_inputs_public_signed)?;
+    verify_segment(&mut segments, &keyload_a_link, Access::Granted, &msg_inputs_private_signed)?;
+
+    // Subscriber A can now reply in the private branch with tagged packets of their own
+    let msg_inputs_private_tagged = vec!["Tagged", "Packets", "From", "Subscriber", "A"];
+    for input in &msg_inputs_private_tagged {
+        let (msg_link, seq_link) = subscriber_a
+            .send_tagged_packet(&prev_msg_link, &Bytes::default(), &Bytes(input.as_bytes().to_vec()))
+            .await?;
+        report.message("tagged from Sub A", &msg_link, seq_link.as_ref());
+        prev_msg_link = msg_link;
+    }
+
+    // The Author only receives the tagged packets of Subscriber A
+    let retrieved = author.fetch_all_next_msgs().await;
+    report.retrieved("Author", retrieved.len());
+    verify_tagged(&msg_inputs_private_tagged, retrieved)?;
+
+    // Subscriber B reads the public chain, but neither the signed nor the tagged packets of the private
+    // branch
+    let retrieved = subscriber_b.fetch_all_next_msgs().await;
+    report.retrieved("SubscriberB", retrieved.len());
+    let mut segments = split_at_keyloads(retrieved);
+    verify_public_chain(&mut segments, &author_pk, &msg_inputs_public_tagged, &msg_inputs_public_signed)?;
+    verify_segment(&mut segments, &keyload_a_link, Access::Denied, &[])?;
+    report.phase("fetch");
+
+    Ok(report)
+}
+
+// The public chain is retrieved before any keyload, signed by the Author where it was signed at all
+fn verify_public_chain(
+    segments: &mut Vec<Segment>,
+    author_pk: &PublicKey,
+    tagged_msgs: &[&str],
+    signed_msgs: &[&str],
+) -> Result<()> {
+    let public = match segments.iter().position(|segment| segment.keyload.is_none()) {
+        Some(index) => segments.remove(index),
+        None => anyhow::bail!("No messages were retrieved before the keyload"),
+    };
+    let mut groups = group_by_publisher(public.messages, std::slice::from_ref(author_pk));
+    verify_signed(signed_msgs, groups.take(author_pk))?;
+    verify_tagged(tagged_msgs, groups.unsigned)?;
+    Ok(())
+}
@@ -0,0 +1,285 @@
+use crate::payload::decompress;
+use iota_streams::{
+    app_channels::api::tangle::{MessageContent, UnwrappedMessage},
+    core::Result,
+};
+use std::fmt;
+
+/// Payload of a packet that is compared against the sent messages
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Payload {
+    Public,
+    Masked,
+}
+
+/// Packets whose payloads are compared against the sent messages. Packets of any other kind count as
+/// extra messages
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Packets {
+    Signed,
+    Tagged,
+    Any,
+}
+
+/// What a verification checks. Keyloads, sequencing and other control messages are never compared
+#[derive(Clone, Copy, Debug)]
+pub struct VerifyOptions {
+    pub payload: Payload,
+    pub packets: Packets,
+    /// Whether messages the reader could not unwrap are accepted rather than reported
+    pub allow_unreadable: bool,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        VerifyOptions {
+            payload: Payload::Masked,
+            packets: Packets::Any,
+            allow_unreadable: false,
+        }
+    }
+}
+
+/// A sent or retrieved message, with its position in the sent or retrieved list
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Positioned {
+    pub index: usize,
+    pub message: String,
+}
+
+/// A message that was retrieved, but not in the position it was sent in
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reordered {
+    pub message: String,
+    pub sent_index: usize,
+    pub retrieved_index: usize,
+}
+
+/// Every difference between the sent and retrieved messages. Indexes of retrieved messages refer to
+/// the full retrieved list, control messages included
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerificationError {
+    pub missing: Vec<Positioned>,
+    pub extra: Vec<Positioned>,
+    pub reordered: Vec<Reordered>,
+    /// Retrieved payloads that are not valid UTF-8, hex encoded
+    pub non_utf8: Vec<Positioned>,
+    pub unreadable: Vec<usize>,
+    /// Retrieved payloads that could not be decompressed, with the reason why
+    pub corrupt: Vec<Positioned>,
+}
+
+impl VerificationError {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty()
+            && self.extra.is_empty()
+            && self.reordered.is_empty()
+            && self.non_utf8.is_empty()
+            && self.unreadable.is_empty()
+            && self.corrupt.is_empty()
+    }
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Retrieved messages do not match the sent messages")?;
+        for msg in &self.missing {
+            write!(f, "\n\tmissing: sent msg {} {:?}", msg.index, msg.message)?;
+        }
+        for msg in &self.extra {
+            write!(f, "\n\textra: retrieved msg {} {:?}", msg.index, msg.message)?;
+        }
+        for msg in &self.reordered {
+            write!(
+                f,
+                "\n\treordered: {:?} sent as msg {}, retrieved as msg {}",
+                msg.message, msg.sent_index, msg.retrieved_index
+            )?;
+        }
+        for msg in &self.non_utf8 {
+            write!(f, "\n\tnon utf8: retrieved msg {} 0x{}", msg.index, msg.message)?;
+        }
+        for index in &self.unreadable {
+            write!(f, "\n\tunreadable: retrieved msg {}", index)?;
+        }
+        for msg in &self.corrupt {
+            write!(f, "\n\tcorrupt: retrieved msg {} ({})", msg.index, msg.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+/// Check that the masked payloads of the retrieved packets are the sent messages, in order. Public payloads
+/// are not compared, see `verify_public`
+pub fn verify_messages(sent_msgs: &[&str], retrieved_msgs: Vec<UnwrappedMessage>) -> Result<()> {
+    verify_with(sent_msgs, &retrieved_msgs, &VerifyOptions::default())?;
+    Ok(())
+}
+
+/// Check that the public payloads of the retrieved packets are the sent messages, in order
+pub fn verify_public(sent_msgs: &[&str], retrieved_msgs: Vec<UnwrappedMessage>) -> Result<()> {
+    let options = VerifyOptions {
+        payload: Payload::Public,
+        ..VerifyOptions::default()
+    };
+    verify_with(sent_msgs, &retrieved_msgs, &options)?;
+    Ok(())
+}
+
+/// Check that the masked payloads of the retrieved signed packets are the sent messages, in order. Tagged
+/// packets are reported as extra messages
+pub fn verify_signed(sent_msgs: &[&str], retrieved_msgs: Vec<UnwrappedMessage>) -> Result<()> {
+    let options = VerifyOptions {
+        packets: Packets::Signed,
+        ..VerifyOptions::default()
+    };
+    verify_with(sent_msgs, &retrieved_msgs, &options)?;
+    Ok(())
+}
+
+/// Check that the masked payloads of the retrieved tagged packets are the sent messages, in order. Signed
+/// packets are reported as extra messages
+pub fn verify_tagged(sent_msgs: &[&str], retrieved_msgs: Vec<UnwrappedMessage>) -> Result<()> {
+    let options = VerifyOptions {
+        packets: Packets::Tagged,
+        ..VerifyOptions::default()
+    };
+    verify_with(sent_msgs, &retrieved_msgs, &options)?;
+    Ok(())
+}
+
+/// Check that none of the retrieved packets could be read, i.e. by a reader without access to them
+pub fn verify_unreadable(retrieved_msgs: Vec<UnwrappedMessage>) -> Result<()> {
+    let options = VerifyOptions {
+        allow_unreadable: true,
+        ..VerifyOptions::default()
+    };
+    verify_with(&[], &retrieved_msgs, &options)?;
+    Ok(())
+}
+
+/// Compare the retrieved messages with the sent messages, reporting every difference rather than the
+/// first one. Compressed payloads are decompressed before they are compared
+pub fn verify_with(
+    sent_msgs: &[&str],
+    retrieved_msgs: &[UnwrappedMessage],
+    options: &VerifyOptions,
+) -> std::result::Result<(), VerificationError> {
+    let mut error = VerificationError::default();
+
+    let mut retrieved = Vec::new();
+    for (index, msg) in retrieved_msgs.iter().enumerate() {
+        let (public_payload, masked_payload) = match (&msg.body, options.packets) {
+            (MessageContent::SignedPacket { public_payload, masked_payload, .. }, Packets::Signed | Packets::Any)
+            | (MessageContent::TaggedPacket { public_payload, masked_payload }, Packets::Tagged | Packets::Any) => {
+                (public_payload, masked_payload)
+            }
+            (MessageContent::SignedPacket { .. }, _) | (MessageContent::TaggedPacket { .. }, _) => {
+                error.extra.push(Positioned {
+                    index,
+                    message: describe(&msg.body).to_string(),
+                });
+                continue;
+            }
+            (MessageContent::Unreadable(_), _) => {
+                if !options.allow_unreadable {
+                    error.unreadable.push(index);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+
+        let (public_payload, masked_payload) = match decompress(public_payload, masked_payload) {
+            Ok(payloads) => payloads,
+            Err(e) => {
+                error.corrupt.push(Positioned {
+                    index,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let payload = match options.payload {
+            Payload::Public => public_payload,
+            Payload::Masked => masked_payload,
+        };
+        match String::from_utf8(payload.0) {
+            Ok(message) => retrieved.push(Positioned { index, message }),
+            Err(e) => error.non_utf8.push(Positioned {
+                index,
+                message: hex::encode(e.as_bytes()),
+            }),
+        }
+    }
+
+    diff(sent_msgs, &retrieved, &mut error);
+    if error.is_empty() {
+        Ok(())
+    } else {
+        Err(error)
+    }
+}
+
+fn describe(content: &MessageContent) -> &'static str {
+    match content {
+        MessageContent::SignedPacket { .. } => "<signed packet>",
+        MessageContent::TaggedPacket { .. } => "<tagged packet>",
+        _ => "<message>",
+    }
+}
+
+// Messages in the longest common subsequence of the sent and retrieved lists are in order. Of the
+// others, those that were both sent and retrieved were reordered, the rest are missing or extra.
+fn diff(sent: &[&str], retrieved: &[Positioned], error: &mut VerificationError) {
+    let (n, m) = (sent.len(), retrieved.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if sent[i] == retrieved[j].message {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut sent_matched = vec![false; n];
+    let mut retrieved_matched = vec![false; m];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if sent[i] == retrieved[j].message {
+            sent_matched[i] = true;
+            retrieved_matched[j] = true;
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    for (msg, _) in retrieved.iter().zip(retrieved_matched).filter(|(_, matched)| !matched) {
+        match (0..n).find(|i| !sent_matched[*i] && sent[*i] == msg.message) {
+            Some(i) => {
+                sent_matched[i] = true;
+                error.reordered.push(Reordered {
+                    message: msg.message.clone(),
+                    sent_index: i,
+                    retrieved_index: msg.index,
+                });
+            }
+            None => error.extra.push(msg.clone()),
+        }
+    }
+
+    for (i, msg) in sent.iter().enumerate().filter(|(i, _)| !sent_matched[*i]) {
+        error.missing.push(Positioned {
+            index: i,
+            message: msg.to_string(),
+        });
+    }
+}
@@ -0,0 +1,275 @@
+use crate::{examples::ExampleReport, transports::RecordingTransport};
+use iota_streams::{
+    app_channels::api::tangle::{Address, MessageContent, PublicKey, Subscriber, Transport},
+    core::Result,
+};
+use serde::Serialize;
+use std::{collections::HashMap, fmt::Write, str::FromStr};
+
+/// Kind of a message in a channel
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum NodeKind {
+    Announce,
+    Keyload,
+    SignedPacket,
+    TaggedPacket,
+    Sequence,
+    /// A message the walking reader could neither unwrap nor resolve as a sequencing message, i.e. a packet
+    /// in a branch it has no access to
+    Unreadable,
+}
+
+impl NodeKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeKind::Announce => "Announce",
+            NodeKind::Keyload => "Keyload",
+            NodeKind::SignedPacket => "SignedPacket",
+            NodeKind::TaggedPacket => "TaggedPacket",
+            NodeKind::Sequence => "Sequence",
+            NodeKind::Unreadable => "Unreadable",
+        }
+    }
+}
+
+/// A message of the channel
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Node {
+    pub link: String,
+    pub kind: NodeKind,
+    /// Name of the identity that published the message, if the reader could tell
+    pub publisher: Option<String>,
+    /// Label of the message in the report of the run that sent it
+    pub label: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum EdgeKind {
+    /// The message was linked to the previous message when it was sent
+    Linked,
+    /// The sequencing message references the message
+    Sequence,
+}
+
+/// An edge between two nodes, by index. Linked edges point from the previous message to the message
+/// linked to it, sequence edges from the sequencing message to the message it references
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub kind: EdgeKind,
+}
+
+/// The message DAG of a channel, with nodes in the order the walking reader fetched them
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ChannelGraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+impl ChannelGraph {
+    /// Index of the node of the message
+    pub fn find(&self, link: &Address) -> Option<usize> {
+        let link = link.to_string();
+        self.nodes.iter().position(|node| node.link == link)
+    }
+
+    pub fn node(&self, link: &Address) -> Option<&Node> {
+        self.find(link).map(|index| &self.nodes[index])
+    }
+
+    /// Label the nodes of the announcement, subscription, keyload and message links of the report
+    pub fn label_from(&mut self, report: &ExampleReport) {
+        let reports = report
+            .announcement
+            .iter()
+            .chain(&report.subscriptions)
+            .chain(&report.keyloads)
+            .chain(&report.messages);
+        for link in reports {
+            if let Some(node) = self.nodes.iter_mut().find(|node| node.link == link.link) {
+                node.label = Some(link.label.clone());
+            }
+        }
+    }
+
+    /// Graphviz DOT rendering of the graph. Linked messages are drawn with solid edges, sequencing messages
+    /// with dashed edges to the message they reference, and each node shows its label and publisher
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph channel {\n    node [shape=box, fontname=\"monospace\"];\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let label = node_lines(node).join("\\n").replace('"', "\\\"");
+            let style = match node.kind {
+                NodeKind::Sequence => ", style=dashed",
+                NodeKind::Unreadable => ", style=dotted",
+                _ => "",
+            };
+            let _ = writeln!(dot, "    n{} [label=\"{}\"{}];", index, label, style);
+        }
+        for edge in &self.edges {
+            let style = match edge.kind {
+                EdgeKind::Linked => "",
+                EdgeKind::Sequence => " [style=dashed]",
+            };
+            let _ = writeln!(dot, "    n{} -> n{}{};", edge.from, edge.to, style);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Mermaid flowchart rendering of the graph
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = String::from("graph TD\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let label = node_lines(node).join("<br/>").replace('"', "#quot;");
+            let _ = writeln!(mermaid, "    n{}[\"{}\"]", index, label);
+        }
+        for edge in &self.edges {
+            let arrow = match edge.kind {
+                EdgeKind::Linked => "-->",
+                EdgeKind::Sequence => "-.->",
+            };
+            let _ = writeln!(mermaid, "    n{} {} n{}", edge.from, arrow, edge.to);
+        }
+        mermaid
+    }
+}
+
+// Kind, label, publisher and shortened message id of a node
+fn node_lines(node: &Node) -> Vec<String> {
+    let msg_id = node.link.rsplit(':').next().unwrap_or_default();
+    let mut lines = vec![node.kind.name().to_string()];
+    lines.extend(node.label.clone());
+    lines.extend(node.publisher.clone());
+    lines.push(msg_id.chars().take(8).collect());
+    lines
+}
+
+/// Reconstructs the message DAG of a channel by reading it as a subscriber. Every message the reader
+/// fetches is recorded along with the link it was attached to, and sequencing messages are resolved
+/// to the message they reference.
+///
+/// The reader only unwraps what it has access to: packets in other branches are kept as unreadable
+/// nodes, without a publisher. Store pre shared keys with [`GraphWalker::reader`] to see more.
+pub struct GraphWalker<T> {
+    reader: Subscriber<RecordingTransport<T>>,
+    recording: RecordingTransport<T>,
+    publishers: HashMap<Vec<u8>, String>,
+}
+
+impl<T: Transport> GraphWalker<T> {
+    pub fn new(transport: T) -> Self {
+        let recording = RecordingTransport::new(transport);
+        GraphWalker {
+            reader: Subscriber::new("GraphWalker", recording.clone()),
+            recording,
+            publishers: HashMap::new(),
+        }
+    }
+
+    /// Name the identity with the public key in the graph. Unnamed signers are shown by the start of
+    /// their public key, and the channel author as "Author"
+    pub fn publisher(&mut self, name: &str, pk: &PublicKey) -> &mut Self {
+        self.publishers.insert(pk.as_bytes().to_vec(), name.to_string());
+        self
+    }
+
+    /// The subscriber reading the channel
+    pub fn reader(&mut self) -> &mut Subscriber<RecordingTransport<T>> {
+        &mut self.reader
+    }
+
+    fn name(&self, pk: &PublicKey) -> String {
+        match self.publishers.get(&pk.as_bytes()[..]) {
+            Some(name) => name.clone(),
+            None => hex::encode(&pk.as_bytes()[..4]),
+        }
+    }
+
+    /// Read the channel from its announcement and return its graph
+    pub async fn walk(mut self, announcement_link: &Address) -> Result<ChannelGraph> {
+        self.reader.receive_announcement(announcement_link).await?;
+        let author = match self.reader.author_public_key() {
+            Some(pk) if self.publishers.contains_key(&pk.as_bytes()[..]) => self.name(pk),
+            _ => "Author".to_string(),
+        };
+
+        let mut unwrapped = HashMap::new();
+        for msg in self.reader.fetch_all_next_msgs().await {
+            let (kind, publisher) = match &msg.body {
+                MessageContent::Keyload => (NodeKind::Keyload, Some(author.clone())),
+                MessageContent::SignedPacket { pk, .. } => (NodeKind::SignedPacket, Some(self.name(pk))),
+                MessageContent::TaggedPacket { .. } => (NodeKind::TaggedPacket, None),
+                _ => (NodeKind::Unreadable, None),
+            };
+            unwrapped.insert(msg.link.to_string(), (kind, publisher));
+        }
+
+        // Recorded messages that were not handed out by the fetch are sequencing messages if they resolve
+        // as one, and unreadable otherwise
+        let mut graph = ChannelGraph::default();
+        let mut indexes = HashMap::new();
+        let mut sequences = Vec::new();
+        let recorded = self.recording.fixture().messages;
+        for msg in &recorded {
+            if indexes.contains_key(&msg.link) {
+                continue;
+            }
+            let (kind, publisher) = if msg.link == announcement_link.to_string() {
+                (NodeKind::Announce, Some(author.clone()))
+            } else if let Some(unwrapped) = unwrapped.remove(&msg.link) {
+                unwrapped
+            } else {
+                let referenced = match Address::from_str(&msg.link) {
+                    Ok(link) => self.reader.receive_sequence(&link).await.ok(),
+                    Err(_) => None,
+                };
+                match referenced {
+                    Some(referenced) => {
+                        sequences.push((graph.nodes.len(), referenced.to_string()));
+                        (NodeKind::Sequence, None)
+                    }
+                    None => (NodeKind::Unreadable, None),
+                }
+            };
+            indexes.insert(msg.link.clone(), graph.nodes.len());
+            graph.nodes.push(Node {
+                link: msg.link.clone(),
+                kind,
+                publisher,
+                label: None,
+            });
+        }
+
+        for msg in &recorded {
+            if let (Some(&from), Some(&to)) = (indexes.get(&msg.prev_link), indexes.get(&msg.link)) {
+                let edge = Edge {
+                    from,
+                    to,
+                    kind: EdgeKind::Linked,
+                };
+                if from != to && !graph.edges.contains(&edge) {
+                    graph.edges.push(edge);
+                }
+            }
+        }
+
+        // A sequencing message is published by whoever published the message it references
+        for (from, referenced) in sequences {
+            if let Some(&to) = indexes.get(&referenced) {
+                graph.nodes[from].publisher = graph.nodes[to].publisher.clone();
+                graph.edges.push(Edge {
+                    from,
+                    to,
+                    kind: EdgeKind::Sequence,
+                });
+            }
+        }
+        Ok(graph)
+    }
+}
+
+/// Graph of the channel, as read by a subscriber without any keys
+pub async fn walk<T: Transport>(transport: T, announcement_link: &Address) -> Result<ChannelGraph> {
+    GraphWalker::new(transport).walk(announcement_link).await
+}
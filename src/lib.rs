@@ -1 +1,13 @@
+pub mod bench;
+pub mod chunking;
 pub mod examples;
+pub mod graph;
+pub mod local_node;
+pub mod payload;
+pub mod publisher;
+pub mod query;
+pub mod reader;
+pub mod runner;
+pub mod state;
+pub mod stream;
+pub mod transports;
@@ -0,0 +1,248 @@
+//! A minimal stand-in for a node, implementing only the subset of the node HTTP API that the
+//! Streams tangle `Client` uses: node info, tips, message submission, finding messages by index and
+//! fetching message data and metadata. Messages are not validated, no proof of work is checked and
+//! nothing is gossiped, so it is only suitable for running examples and tests locally.
+//!
+//! Malformed requests are answered with a `400` JSON error, and bodies over `MAX_BODY_SIZE` with a `413`
+//! before they are read.
+
+use iota_streams::core::Result;
+use serde_json::{json, Value};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+pub mod store;
+pub use store::Store;
+
+/// Port used by nodes for their HTTP API
+pub const DEFAULT_PORT: u16 = 14265;
+
+/// Largest request body the node reads, well above the size of any message the client submits
+pub const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+const NETWORK_ID: &str = "streams-examples-local";
+
+// Status line and JSON body of a response
+type Response = (&'static str, Value);
+
+pub struct LocalNode {
+    listener: TcpListener,
+    store: Arc<Mutex<Store>>,
+}
+
+impl LocalNode {
+    pub fn bind<A: Into<SocketAddr>>(addr: A, store: Store) -> Result<Self> {
+        Ok(LocalNode {
+            listener: TcpListener::bind(addr.into())?,
+            store: Arc::new(Mutex::new(store)),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Url the node can be reached at, i.e. for use with `transports::node`
+    pub fn url(&self) -> Result<String> {
+        Ok(format!("http://{}", self.local_addr()?))
+    }
+
+    /// Serve requests until the process exits, handling each connection in its own thread
+    pub fn serve(self) {
+        for stream in self.listener.incoming().flatten() {
+            let store = self.store.clone();
+            thread::spawn(move || {
+                if let Err(e) = handle_connection(stream, &store) {
+                    eprintln!("Failed to handle request: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Serve requests from a background thread, returning the url the node can be reached at
+    pub fn spawn(self) -> Result<String> {
+        let url = self.url()?;
+        thread::spawn(move || self.serve());
+        Ok(url)
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: Option<String>,
+    body: Vec<u8>,
+}
+
+// Requests that cannot be read are answered with an error response rather than routed
+fn read_request(stream: &mut TcpStream) -> std::result::Result<Request, Response> {
+    let malformed = |e: std::io::Error| error("400 Bad Request", &format!("Malformed request: {}", e));
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).map_err(malformed)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (target.to_string(), None),
+    };
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).map_err(malformed)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| error("400 Bad Request", "Invalid Content-Length"))?;
+            }
+        }
+    }
+
+    // The body is only allocated once its announced length is known to be reasonable
+    if content_length > MAX_BODY_SIZE {
+        return Err(error("413 Payload Too Large", "Request body too large"));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(malformed)?;
+
+    Ok(Request {
+        method,
+        path,
+        query,
+        body,
+    })
+}
+
+fn handle_connection(mut stream: TcpStream, store: &Mutex<Store>) -> Result<()> {
+    let (status, body) = match read_request(&mut stream) {
+        Ok(request) => route(&request, store).unwrap_or_else(|e| error("500 Internal Server Error", &e.to_string())),
+        Err(response) => response,
+    };
+
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn route(request: &Request, store: &Mutex<Store>) -> Result<Response> {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let mut store = store.lock().map_err(|_| anyhow::anyhow!("Message store poisoned"))?;
+
+    let response = match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["health"]) => ("200 OK", Value::Null),
+        ("GET", ["api", "v1", "info"]) => ("200 OK", data(info())),
+        ("GET", ["api", "v1", "tips"]) => (
+            "200 OK",
+            data(json!({ "tipMessageIds": [format!("{:064x}", 0)] })),
+        ),
+        ("POST", ["api", "v1", "messages"]) => {
+            match serde_json::from_slice(&request.body) {
+                Ok(message) => {
+                    let id = store.submit(message)?;
+                    ("201 Created", data(json!({ "messageId": id })))
+                }
+                Err(e) => error("400 Bad Request", &format!("Invalid message: {}", e)),
+            }
+        }
+        ("GET", ["api", "v1", "messages"]) => {
+            let index = request
+                .query
+                .as_deref()
+                .unwrap_or_default()
+                .split('&')
+                .find_map(|param| param.strip_prefix("index="));
+            match index {
+                Some(index) => {
+                    let ids = store.find_by_index(index);
+                    (
+                        "200 OK",
+                        data(json!({
+                            "index": index,
+                            "maxResults": 1000,
+                            "count": ids.len(),
+                            "messageIds": ids,
+                        })),
+                    )
+                }
+                None => not_found("Missing index query parameter"),
+            }
+        }
+        ("GET", ["api", "v1", "messages", id]) => match store.get(id) {
+            Some(message) => ("200 OK", data(message.clone())),
+            None => not_found("Message not found"),
+        },
+        ("GET", ["api", "v1", "messages", id, "metadata"]) => match store.get(id) {
+            Some(message) => ("200 OK", data(metadata(id, message))),
+            None => not_found("Message not found"),
+        },
+        _ => not_found("Endpoint not supported by the local node"),
+    };
+    Ok(response)
+}
+
+fn data(value: Value) -> Value {
+    json!({ "data": value })
+}
+
+fn not_found(message: &str) -> Response {
+    error("404 Not Found", message)
+}
+
+fn error(status: &'static str, message: &str) -> Response {
+    let code = status.split(' ').next().unwrap_or_default();
+    (status, json!({ "error": { "code": code, "message": message } }))
+}
+
+fn info() -> Value {
+    json!({
+        "name": "streams-examples-local-node",
+        "version": env!("CARGO_PKG_VERSION"),
+        "isHealthy": true,
+        "networkId": NETWORK_ID,
+        "bech32HRP": "atoi",
+        "minPowScore": 1.0,
+        "messagesPerSecond": 0.0,
+        "referencedMessagesPerSecond": 0.0,
+        "referencedRate": 0.0,
+        "latestMilestoneTimestamp": 0,
+        "latestMilestoneIndex": 0,
+        "confirmedMilestoneIndex": 0,
+        "pruningIndex": 0,
+        "features": [],
+    })
+}
+
+// Every stored message is reported as solid and included, as there is no milestone process
+fn metadata(id: &str, message: &Value) -> Value {
+    json!({
+        "messageId": id,
+        "parentMessageIds": message.get("parentMessageIds").cloned().unwrap_or_else(|| json!([])),
+        "isSolid": true,
+        "referencedByMilestoneIndex": 0,
+        "milestoneIndex": null,
+        "ledgerInclusionState": "noTransaction",
+        "conflictReason": null,
+        "shouldPromote": false,
+        "shouldReattach": false,
+    })
+}
@@ -0,0 +1,94 @@
+use iota_streams::core::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+/// A message as submitted to the node, keyed by the id the node assigned to it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredMessage {
+    pub id: String,
+    pub message: Value,
+}
+
+/// Message storage of the local node. Messages are kept in memory and, when a file is provided,
+/// appended to it as JSON lines so they survive a restart.
+#[derive(Default)]
+pub struct Store {
+    messages: HashMap<String, Value>,
+    by_index: HashMap<String, Vec<String>>,
+    file: Option<PathBuf>,
+}
+
+impl Store {
+    pub fn in_memory() -> Self {
+        Store::default()
+    }
+
+    /// Open a store backed by the provided file, loading any messages already stored in it
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut store = Store {
+            file: Some(path.as_ref().to_path_buf()),
+            ..Store::default()
+        };
+        if path.as_ref().exists() {
+            for line in BufReader::new(File::open(path)?).lines() {
+                let stored: StoredMessage = serde_json::from_str(&line?)?;
+                store.insert(stored.id, stored.message);
+            }
+        }
+        Ok(store)
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Store a submitted message, returning the id assigned to it
+    pub fn submit(&mut self, message: Value) -> Result<String> {
+        let id = format!("{:064x}", self.messages.len() + 1);
+        if let Some(path) = &self.file {
+            let stored = StoredMessage {
+                id: id.clone(),
+                message: message.clone(),
+            };
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", serde_json::to_string(&stored)?)?;
+        }
+        self.insert(id.clone(), message);
+        Ok(id)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Value> {
+        self.messages.get(&id.to_lowercase())
+    }
+
+    /// Ids of the messages carrying an indexation payload with the provided (hex encoded) index
+    pub fn find_by_index(&self, index: &str) -> Vec<String> {
+        self.by_index.get(&index.to_lowercase()).cloned().unwrap_or_default()
+    }
+
+    fn insert(&mut self, id: String, message: Value) {
+        if let Some(index) = indexation_index(&message) {
+            self.by_index.entry(index).or_default().push(id.clone());
+        }
+        self.messages.insert(id, message);
+    }
+}
+
+// Streams messages are sent as indexation payloads, the index being the hex encoded message index
+fn indexation_index(message: &Value) -> Option<String> {
+    message
+        .get("payload")
+        .and_then(|payload| payload.get("index"))
+        .and_then(Value::as_str)
+        .map(str::to_lowercase)
+}
@@ -1,75 +1,223 @@
-use anyhow::Result;
-
-mod examples;
-
-#[tokio::main]
-async fn main() -> Result<()> {
-    let url = "https://chrysalis-nodes.iota.org";
-
-    println!("Starting Examples");
-    println!("---------------------------------------");
-    println!("Single Publisher Examples");
-
-    println!("\n---------------------------------------");
-    println!("\nPublic - Single Branch - Single Publisher\n");
-    examples::single_branch_public::example(url).await?;
-
-    println!("\n---------------------------------------");
-    println!("\nPrivate - Single Branch - Single Publisher\n");
-    examples::single_branch_private::example(url).await?;
-
-    println!("\n---------------------------------------");
-    println!("\nPublic - Single Depth - Single Publisher\n");
-    examples::single_depth_public::example(url).await?;
-
-    println!("\n---------------------------------------");
-    println!("\nPrivate - Single Depth - Single Publisher\n");
-    examples::single_depth_private::example(url).await?;
-
-    println!("\n---------------------------------------");
-    println!("\nMixed - Multi Branch - Single Publisher\n");
-    examples::multi_branch_mixed_privacy::example(url).await?;
-
-    println!("\n---------------------------------------");
-    println!("Multiple Publisher Examples");
-
-    println!("\n---------------------------------------");
-    println!("\nPrivate - Multi Branch - Single Publisher per Branch\n");
-    examples::single_pub_per_branch::example(url).await?;
-
-    println!("\n---------------------------------------");
-    println!("\nPrivate - Multi Branch - Multiple Publishers per Branch\n");
-    examples::multi_pub_per_branch::example(url).await?;
-
-    println!("\n---------------------------------------");
-    println!("Utility Examples");
-
-    println!("\n---------------------------------------");
-    println!("\nPrevious Message Retrieval\n");
-    examples::fetch_prev::example(url).await?;
-
-    println!("\n---------------------------------------");
-    println!("\nGranting and Revoking Access\n");
-    examples::grant_and_revoke_access::example(url).await?;
+use anyhow::{anyhow, bail, Result};
+use iota_streams::app_channels::api::tangle::Address;
+use std::{str::FromStr, time::Instant};
+use streams_examples::{
+    examples::SeedProvider,
+    graph,
+    runner::{self, Example, RunDir, Section, Summary},
+    transports,
+};
+
+const DEFAULT_NODE_URL: &str = "https://chrysalis-nodes.iota.org";
+
+const USAGE: &str = "Usage:
+    streams-examples list
+    streams-examples [run] [EXAMPLE|SECTION ...] [--transport offline|node] [--node-url URL] [--seed SEED]
+                     [--jobs N] [--json FILE]
+    streams-examples graph EXAMPLE [--format dot|mermaid] [--out FILE] [--seed SEED]
+
+Examples are run against an in-memory transport unless `--transport node` or a `--node-url` is
+provided. Without any example or section names, every example is run.
+
+Author seeds and pre shared keys are generated from SEED (or the STREAMS_EXAMPLES_SEED environment
+variable), so a run can be reproduced by providing the seed it reports. Without a seed, a random
+one is picked.
+
+Examples are run one at a time unless `--jobs` allows more of them to run concurrently, on as many
+threads. Every run gets its own transport and temporary directory, so concurrent runs do not share
+any state.
+
+With `--json`, the results and reports of every example are written to FILE as JSON (or to stdout
+when FILE is `-`).
+
+`graph` runs a single example against the in-memory transport, reads its channel back from the
+announcement and prints the message graph as Graphviz DOT or Mermaid (or writes it to FILE).";
+
+enum TransportKind {
+    Offline,
+    Node(String),
+}
 
-    println!("\n---------------------------------------");
-    println!("\nUsing Public Keys for Keyload Generation\n");
-    examples::pk_keyloads::example(url).await?;
+#[derive(Clone, Copy)]
+enum GraphFormat {
+    Dot,
+    Mermaid,
+}
 
-    println!("\n---------------------------------------");
-    println!("\nUsing Pre Shared Keys for Keyload Generation\n");
-    examples::psk_keyloads::example(url).await?;
+struct Options {
+    list: bool,
+    graph: bool,
+    format: GraphFormat,
+    out: Option<String>,
+    names: Vec<String>,
+    transport: TransportKind,
+    seed: Option<u64>,
+    jobs: usize,
+    json: Option<String>,
+}
 
-    println!("\n---------------------------------------");
-    println!("\nState Recovery\n");
-    examples::state_recovery::example(url).await?;
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options> {
+    let mut list = false;
+    let mut graph = false;
+    let mut format = GraphFormat::Dot;
+    let mut out = None;
+    let mut names = Vec::new();
+    let mut node = false;
+    let mut node_url = None;
+    let mut seed = None;
+    let mut jobs = 1;
+    let mut json = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "list" if names.is_empty() => list = true,
+            "run" if names.is_empty() => {}
+            "graph" if names.is_empty() => graph = true,
+            "--format" => match args.next().as_deref() {
+                Some("dot") => format = GraphFormat::Dot,
+                Some("mermaid") => format = GraphFormat::Mermaid,
+                other => bail!("Unknown graph format: {}", other.unwrap_or_default()),
+            },
+            "--out" => match args.next() {
+                Some(path) => out = Some(path),
+                None => bail!("Missing value for --out"),
+            },
+            "--transport" => match args.next().as_deref() {
+                Some("offline") => node = false,
+                Some("node") => node = true,
+                other => bail!("Unknown transport: {}", other.unwrap_or_default()),
+            },
+            "--node-url" => match args.next() {
+                Some(url) => {
+                    node = true;
+                    node_url = Some(url);
+                }
+                None => bail!("Missing value for --node-url"),
+            },
+            "--seed" => match args.next() {
+                Some(value) => seed = Some(value.parse()?),
+                None => bail!("Missing value for --seed"),
+            },
+            "--jobs" => match args.next() {
+                Some(value) => jobs = value.parse()?,
+                None => bail!("Missing value for --jobs"),
+            },
+            "--json" => match args.next() {
+                Some(path) => json = Some(path),
+                None => bail!("Missing value for --json"),
+            },
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            flag if flag.starts_with("--") => bail!("Unknown option: {}\n\n{}", flag, USAGE),
+            name => names.push(name.to_string()),
+        }
+    }
+
+    let transport = if node {
+        TransportKind::Node(node_url.unwrap_or_else(|| DEFAULT_NODE_URL.to_string()))
+    } else {
+        TransportKind::Offline
+    };
+
+    Ok(Options {
+        list,
+        graph,
+        format,
+        out,
+        names,
+        transport,
+        seed,
+        jobs,
+        json,
+    })
+}
 
-    println!("\n---------------------------------------");
-    println!("\nStateless Recovery\n");
-    examples::stateless_recovery::example(url).await?;
+fn list() {
+    for section in Section::ALL.iter() {
+        println!("{} ({})", section.title(), section.name());
+        for example in Example::ALL.iter().filter(|e| e.section() == *section) {
+            println!("\t{:<28} {}", example.name(), example.title());
+        }
+    }
+}
 
-    println!("\n---------------------------------------");
-    println!("Examples Complete");
+async fn graph(example: Example, seeds: &SeedProvider, format: GraphFormat, out: Option<&str>) -> Result<()> {
+    let transport = transports::offline();
+    let dir = RunDir::new(example.name())?;
+    let report = example
+        .run(transport.clone(), &mut seeds.for_example(example.name()), dir.path())
+        .await?;
+    let announcement = report
+        .announcement
+        .as_ref()
+        .ok_or_else(|| anyhow!("{} did not report an announcement", example.name()))?;
+
+    let mut graph = graph::walk(transport, &Address::from_str(&announcement.link)?).await?;
+    graph.label_from(&report);
+    let rendered = match format {
+        GraphFormat::Dot => graph.to_dot(),
+        GraphFormat::Mermaid => graph.to_mermaid(),
+    };
+    match out {
+        Some(path) => std::fs::write(path, rendered)?,
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
 
+#[tokio::main]
+async fn main() -> Result<()> {
+    let options = parse_args(std::env::args().skip(1))?;
+    if options.list {
+        list();
+        return Ok(());
+    }
+
+    let selected = runner::select(&options.names)?;
+    let seed = match options.seed {
+        Some(seed) => seed,
+        None => SeedProvider::from_env()?.seed().unwrap_or_else(rand::random),
+    };
+    let seeds = SeedProvider::deterministic(seed);
+
+    if options.graph {
+        match selected.as_slice() {
+            [example] if !options.names.is_empty() => {
+                return graph(*example, &seeds, options.format, options.out.as_deref()).await
+            }
+            _ => bail!("graph takes a single example name\n\n{}", USAGE),
+        }
+    }
+
+    println!("Starting Examples with seed {}", seed);
+    let start = Instant::now();
+    let results = match &options.transport {
+        TransportKind::Offline => runner::run_all(&selected, transports::offline, &seeds, options.jobs).await,
+        TransportKind::Node(url) => {
+            let url = url.clone();
+            runner::run_all(&selected, move || transports::node(&url), &seeds, options.jobs).await
+        }
+    };
+    let elapsed = start.elapsed();
+
+    println!("\n---------------------------------------");
+    println!("Examples Complete\n");
+    println!("{}", Summary(&results));
+    println!("Wall time with {} job(s): {:.2}s", options.jobs, elapsed.as_secs_f64());
+    println!("\nReproduce this run with --seed {}", seed);
+
+    if let Some(path) = &options.json {
+        let json = serde_json::to_string_pretty(&runner::to_json(&results, Some(seed)))?;
+        match path.as_str() {
+            "-" => println!("{}", json),
+            path => std::fs::write(path, json)?,
+        }
+    }
+
+    if results.iter().any(|r| !r.passed()) {
+        std::process::exit(1);
+    }
     Ok(())
 }
@@ -0,0 +1,216 @@
+use crate::payload::compression::{decompress, CompressionError};
+use iota_streams::app_channels::api::tangle::{Bytes, MessageContent, UnwrappedMessage};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{convert::TryFrom, fmt};
+
+// Marks a payload as written by the codec, ahead of the format, schema and version
+const MAGIC: &[u8; 2] = b"tp";
+
+/// A value that can be sent as a typed payload. The schema id and version are written ahead of the
+/// serialized value, so readers can tell what a payload holds before decoding it
+pub trait Record: Serialize + DeserializeOwned {
+    const SCHEMA: &'static str;
+    const VERSION: u16;
+}
+
+/// Serialization format of a typed payload
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Cbor,
+    Bincode,
+}
+
+impl Format {
+    pub const ALL: [Format; 3] = [Format::Json, Format::Cbor, Format::Bincode];
+
+    fn id(&self) -> u8 {
+        match self {
+            Format::Json => 0,
+            Format::Cbor => 1,
+            Format::Bincode => 2,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        Format::ALL.iter().copied().find(|format| format.id() == id)
+    }
+}
+
+/// The format, schema id and version a typed payload was written with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tag {
+    pub format: Format,
+    pub schema: String,
+    pub version: u16,
+}
+
+/// Reasons a payload could not be encoded or decoded into a typed value
+#[derive(Debug)]
+pub enum CodecError {
+    /// The message is not a signed or tagged packet
+    NotAPacket,
+    /// The reader could not unwrap the message
+    Unreadable,
+    /// The payload is empty, i.e. nothing was sent in it
+    Empty,
+    /// The payload was not written by the codec
+    Untyped,
+    /// The payload ends before its tag does
+    Truncated,
+    UnknownFormat(u8),
+    SchemaTooLong(usize),
+    SchemaMismatch { expected: &'static str, found: String },
+    VersionMismatch { schema: String, expected: u16, found: u16 },
+    Json(serde_json::Error),
+    Cbor(serde_cbor::Error),
+    Bincode(bincode::Error),
+    Compression(CompressionError),
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::NotAPacket => write!(f, "Message is not a signed or tagged packet"),
+            CodecError::Unreadable => write!(f, "Message could not be unwrapped by the reader"),
+            CodecError::Empty => write!(f, "Payload is empty"),
+            CodecError::Untyped => write!(f, "Payload is not a typed payload"),
+            CodecError::Truncated => write!(f, "Payload ends before its tag"),
+            CodecError::UnknownFormat(id) => write!(f, "Unknown payload format {}", id),
+            CodecError::SchemaTooLong(len) => write!(f, "Schema id of {} bytes is longer than 255 bytes", len),
+            CodecError::SchemaMismatch { expected, found } => {
+                write!(f, "Expected a {} payload, found {}", expected, found)
+            }
+            CodecError::VersionMismatch {
+                schema,
+                expected,
+                found,
+            } => write!(f, "Expected version {} of {}, found version {}", expected, schema, found),
+            CodecError::Json(e) => write!(f, "JSON: {}", e),
+            CodecError::Cbor(e) => write!(f, "CBOR: {}", e),
+            CodecError::Bincode(e) => write!(f, "bincode: {}", e),
+            CodecError::Compression(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+/// Encodes typed values into packet payloads in a single format. Decoding reads the format from the
+/// payload, so any codec (or [`decode_public`] and [`decode_masked`]) decodes payloads of any format
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Codec {
+    pub format: Format,
+}
+
+impl Codec {
+    pub fn new(format: Format) -> Self {
+        Codec { format }
+    }
+
+    /// Serialize the value into a payload tagged with its schema id and version
+    pub fn encode<R: Record>(&self, value: &R) -> Result<Bytes, CodecError> {
+        let schema = R::SCHEMA.as_bytes();
+        let schema_len = u8::try_from(schema.len()).map_err(|_| CodecError::SchemaTooLong(schema.len()))?;
+
+        let mut payload = MAGIC.to_vec();
+        payload.push(self.format.id());
+        payload.push(schema_len);
+        payload.extend_from_slice(schema);
+        payload.extend_from_slice(&R::VERSION.to_be_bytes());
+        match self.format {
+            Format::Json => serde_json::to_writer(&mut payload, value).map_err(CodecError::Json)?,
+            Format::Cbor => serde_cbor::to_writer(&mut payload, value).map_err(CodecError::Cbor)?,
+            Format::Bincode => bincode::serialize_into(&mut payload, value).map_err(CodecError::Bincode)?,
+        }
+        Ok(Bytes(payload))
+    }
+
+    /// Deserialize a payload written by any codec
+    pub fn decode<R: Record>(&self, payload: &Bytes) -> Result<R, CodecError> {
+        decode(payload)
+    }
+}
+
+/// Read the tag of a typed payload without decoding its value
+pub fn tag(payload: &Bytes) -> Result<Tag, CodecError> {
+    split(payload).map(|(tag, _)| tag)
+}
+
+fn split(payload: &Bytes) -> Result<(Tag, &[u8]), CodecError> {
+    let bytes = &payload.0[..];
+    if bytes.is_empty() {
+        return Err(CodecError::Empty);
+    }
+    if !bytes.starts_with(MAGIC) {
+        return Err(CodecError::Untyped);
+    }
+    let (format, schema_len) = match bytes.get(MAGIC.len()..MAGIC.len() + 2) {
+        Some(header) => (header[0], header[1] as usize),
+        None => return Err(CodecError::Truncated),
+    };
+    let format = Format::from_id(format).ok_or(CodecError::UnknownFormat(format))?;
+
+    let schema_start = MAGIC.len() + 2;
+    let body_start = schema_start + schema_len + 2;
+    let (schema, version) = match bytes.get(schema_start..body_start) {
+        Some(tag) => tag.split_at(schema_len),
+        None => return Err(CodecError::Truncated),
+    };
+    let tag = Tag {
+        format,
+        schema: String::from_utf8_lossy(schema).into_owned(),
+        version: u16::from_be_bytes([version[0], version[1]]),
+    };
+    Ok((tag, &bytes[body_start..]))
+}
+
+fn decode<R: Record>(payload: &Bytes) -> Result<R, CodecError> {
+    let (tag, body) = split(payload)?;
+    if tag.schema != R::SCHEMA {
+        return Err(CodecError::SchemaMismatch {
+            expected: R::SCHEMA,
+            found: tag.schema,
+        });
+    }
+    if tag.version != R::VERSION {
+        return Err(CodecError::VersionMismatch {
+            schema: tag.schema,
+            expected: R::VERSION,
+            found: tag.version,
+        });
+    }
+    match tag.format {
+        Format::Json => serde_json::from_slice(body).map_err(CodecError::Json),
+        Format::Cbor => serde_cbor::from_slice(body).map_err(CodecError::Cbor),
+        Format::Bincode => bincode::deserialize(body).map_err(CodecError::Bincode),
+    }
+}
+
+// Public and masked payloads of a packet, decompressed if they were sent compressed
+fn payloads(msg: &UnwrappedMessage) -> Result<(Bytes, Bytes), CodecError> {
+    let (public, masked) = match &msg.body {
+        MessageContent::SignedPacket {
+            public_payload,
+            masked_payload,
+            ..
+        }
+        | MessageContent::TaggedPacket {
+            public_payload,
+            masked_payload,
+        } => (public_payload, masked_payload),
+        MessageContent::Unreadable(_) => return Err(CodecError::Unreadable),
+        _ => return Err(CodecError::NotAPacket),
+    };
+    decompress(public, masked).map_err(CodecError::Compression)
+}
+
+/// Decode the public payload of a retrieved packet
+pub fn decode_public<R: Record>(msg: &UnwrappedMessage) -> Result<R, CodecError> {
+    decode(&payloads(msg)?.0)
+}
+
+/// Decode the masked payload of a retrieved packet
+pub fn decode_masked<R: Record>(msg: &UnwrappedMessage) -> Result<R, CodecError> {
+    decode(&payloads(msg)?.1)
+}
@@ -0,0 +1,183 @@
+use crate::publisher::Publisher;
+use flate2::{read::DeflateDecoder, write::DeflateEncoder};
+use iota_streams::{
+    app_channels::api::tangle::{Address, Bytes},
+    core::Result,
+};
+use std::{
+    convert::TryFrom,
+    fmt,
+    io::{self, Read, Write},
+};
+
+// Marks a public payload as carrying a compression header. Text payloads do not start with a NUL
+const MAGIC: &[u8; 3] = b"\0ce";
+const HEADER_LEN: usize = MAGIC.len() + 5;
+
+/// Largest masked payload, in bytes, that is compressed or decompressed. The decoded size is read from
+/// the header, which any publisher can craft, so larger sizes are rejected before anything is decoded
+pub const MAX_DECOMPRESSED: u32 = 16 * 1024 * 1024;
+
+/// Encoding of a masked payload
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Identity,
+    Zstd,
+    Deflate,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 3] = [Encoding::Identity, Encoding::Zstd, Encoding::Deflate];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Identity => "identity",
+            Encoding::Zstd => "zstd",
+            Encoding::Deflate => "deflate",
+        }
+    }
+
+    fn id(&self) -> u8 {
+        match self {
+            Encoding::Identity => 0,
+            Encoding::Zstd => 1,
+            Encoding::Deflate => 2,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        Encoding::ALL.iter().copied().find(|encoding| encoding.id() == id)
+    }
+}
+
+/// Content-type header written ahead of the public payload: how the masked payload is encoded and
+/// its size once decoded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub encoding: Encoding,
+    pub size: u32,
+}
+
+/// Reasons a compressed payload could not be decompressed
+#[derive(Debug)]
+pub enum CompressionError {
+    /// The public payload ends before its header does
+    Truncated,
+    UnknownEncoding(u8),
+    TooLarge(usize),
+    /// The header records a decoded size above [`MAX_DECOMPRESSED`]
+    ExceedsLimit(u32),
+    /// The masked payload does not decode to the size recorded in the header
+    SizeMismatch { expected: u32, found: usize },
+    Io(io::Error),
+}
+
+impl fmt::Display for CompressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompressionError::Truncated => write!(f, "Public payload ends before its compression header"),
+            CompressionError::UnknownEncoding(id) => write!(f, "Unknown payload encoding {}", id),
+            CompressionError::TooLarge(len) => write!(f, "Payload of {} bytes is too large to compress", len),
+            CompressionError::ExceedsLimit(size) => write!(
+                f,
+                "Payload claims to decode to {} bytes, more than the limit of {}",
+                size, MAX_DECOMPRESSED
+            ),
+            CompressionError::SizeMismatch { expected, found } => {
+                write!(f, "Masked payload decodes to {} bytes, expected {}", found, expected)
+            }
+            CompressionError::Io(e) => write!(f, "Payload could not be decoded: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CompressionError {}
+
+impl From<io::Error> for CompressionError {
+    fn from(e: io::Error) -> Self {
+        CompressionError::Io(e)
+    }
+}
+
+/// Encode the masked payload and prefix the public payload with the header recording the encoding
+pub fn compress(encoding: Encoding, public: &Bytes, masked: &Bytes) -> Result<(Bytes, Bytes), CompressionError> {
+    let size = u32::try_from(masked.0.len())
+        .ok()
+        .filter(|size| *size <= MAX_DECOMPRESSED)
+        .ok_or(CompressionError::TooLarge(masked.0.len()))?;
+    let encoded = match encoding {
+        Encoding::Identity => masked.0.clone(),
+        Encoding::Zstd => zstd::bulk::compress(&masked.0, zstd::DEFAULT_COMPRESSION_LEVEL)?,
+        Encoding::Deflate => {
+            let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&masked.0)?;
+            encoder.finish()?
+        }
+    };
+
+    let mut header = MAGIC.to_vec();
+    header.push(encoding.id());
+    header.extend_from_slice(&size.to_be_bytes());
+    header.extend_from_slice(&public.0);
+    Ok((Bytes(header), Bytes(encoded)))
+}
+
+/// Read the compression header of a public payload, if it has one
+pub fn header(public: &Bytes) -> Result<Option<Header>, CompressionError> {
+    if !public.0.starts_with(MAGIC) {
+        return Ok(None);
+    }
+    let header = public.0.get(MAGIC.len()..HEADER_LEN).ok_or(CompressionError::Truncated)?;
+    let encoding = Encoding::from_id(header[0]).ok_or(CompressionError::UnknownEncoding(header[0]))?;
+    Ok(Some(Header {
+        encoding,
+        size: u32::from_be_bytes([header[1], header[2], header[3], header[4]]),
+    }))
+}
+
+/// Strip the header from the public payload and decode the masked payload. Payloads without a header
+/// are returned as they are
+pub fn decompress(public: &Bytes, masked: &Bytes) -> Result<(Bytes, Bytes), CompressionError> {
+    let header = match header(public)? {
+        Some(header) => header,
+        None => return Ok((public.clone(), masked.clone())),
+    };
+
+    if header.size > MAX_DECOMPRESSED {
+        return Err(CompressionError::ExceedsLimit(header.size));
+    }
+    let expected = header.size as usize;
+    // Decoders are streamed and cut one byte past the recorded size, so a crafted payload can neither
+    // make the reader allocate more than the limit nor go unnoticed when it decodes to more
+    let limit = u64::from(header.size) + 1;
+    let decoded = match header.encoding {
+        Encoding::Identity => masked.0.clone(),
+        Encoding::Zstd => read_limited(zstd::stream::read::Decoder::new(&masked.0[..])?, limit)?,
+        Encoding::Deflate => read_limited(DeflateDecoder::new(&masked.0[..]), limit)?,
+    };
+    if decoded.len() != expected {
+        return Err(CompressionError::SizeMismatch {
+            expected: header.size,
+            found: decoded.len(),
+        });
+    }
+    Ok((Bytes(public.0[HEADER_LEN..].to_vec()), Bytes(decoded)))
+}
+
+fn read_limited<R: Read>(decoder: R, limit: u64) -> io::Result<Vec<u8>> {
+    let mut decoded = Vec::new();
+    decoder.take(limit).read_to_end(&mut decoded)?;
+    Ok(decoded)
+}
+
+/// Send a signed packet with its masked payload encoded
+pub async fn send_compressed<P: Publisher>(
+    publisher: &mut P,
+    link_to: &Address,
+    encoding: Encoding,
+    public: &Bytes,
+    masked: &Bytes,
+) -> Result<(Address, Option<Address>)> {
+    let (public, masked) = compress(encoding, public, masked)?;
+    publisher.send_signed_packet(link_to, &public, &masked).await
+}
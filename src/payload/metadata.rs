@@ -0,0 +1,228 @@
+use crate::{
+    payload::{decode_public, Codec, Format, Record},
+    publisher::Publisher,
+};
+use iota_streams::{
+    app_channels::api::tangle::{Address, Bytes, MessageContent, PublicKey, Subscriber, Transport, UnwrappedMessage},
+    core::Result,
+};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Indexable description of a packet, sent in its public payload
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    pub kind: String,
+    /// Milliseconds since the unix epoch
+    pub timestamp: u64,
+    pub device: String,
+}
+
+impl Record for Metadata {
+    const SCHEMA: &'static str = "metadata";
+    const VERSION: u16 = 1;
+}
+
+impl Metadata {
+    pub fn new(kind: &str, timestamp: u64, device: &str) -> Self {
+        Metadata {
+            kind: kind.to_string(),
+            timestamp,
+            device: device.to_string(),
+        }
+    }
+
+    /// Metadata timestamped with the current time
+    pub fn now(kind: &str, device: &str) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default();
+        Self::new(kind, timestamp, device)
+    }
+}
+
+/// Metadata of a packet in a private branch, published again in a public branch along with the link
+/// of the packet
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub metadata: Metadata,
+    pub data_link: String,
+}
+
+impl Record for IndexEntry {
+    const SCHEMA: &'static str = "metadata.index";
+    const VERSION: u16 = 1;
+}
+
+/// Links of a published record
+#[derive(Clone, Debug)]
+pub struct Published {
+    pub data_link: Address,
+    pub index_link: Address,
+}
+
+/// Publish a record: the data as the masked payload of a packet linked to `data_link_to`, with the
+/// metadata as its public payload, and an index entry linked to `index_link_to`.
+///
+/// Only readers with access to a branch can unwrap its packets, public payloads included. With the
+/// data in a private branch and the index in a public one, anyone reading the channel can list and
+/// filter the records, while only subscribers with access to the private branch can read their data
+pub async fn publish<P: Publisher>(
+    publisher: &mut P,
+    data_link_to: &Address,
+    index_link_to: &Address,
+    metadata: &Metadata,
+    masked: &Bytes,
+) -> Result<Published> {
+    let codec = Codec::new(Format::Json);
+    let (data_link, _seq_link) = publisher
+        .send_signed_packet(data_link_to, &codec.encode(metadata)?, masked)
+        .await?;
+    let entry = IndexEntry {
+        metadata: metadata.clone(),
+        data_link: data_link.to_string(),
+    };
+    let (index_link, _seq_link) = publisher
+        .send_signed_packet(index_link_to, &codec.encode(&entry)?, &Bytes::default())
+        .await?;
+    Ok(Published { data_link, index_link })
+}
+
+/// A record listed by a reader
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub metadata: Metadata,
+    /// Link of the packet holding the data
+    pub data_link: String,
+    /// Link of the index entry, if the record was listed from one
+    pub index_link: Option<String>,
+    pub publisher: Option<PublicKey>,
+    /// Whether metadata and publisher were read from the data packet itself. Otherwise they come from
+    /// an index entry, which anyone publishing on the channel could have sent for any data link
+    pub from_packet: bool,
+}
+
+/// Conditions on the metadata of a record. Unset conditions match anything
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    pub kind: Option<String>,
+    pub device: Option<String>,
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+}
+
+impl Filter {
+    pub fn kind(mut self, kind: &str) -> Self {
+        self.kind = Some(kind.to_string());
+        self
+    }
+
+    pub fn device(mut self, device: &str) -> Self {
+        self.device = Some(device.to_string());
+        self
+    }
+
+    /// Timestamps from `since` up to, but excluding, `until`
+    pub fn between(mut self, since: u64, until: u64) -> Self {
+        self.since = Some(since);
+        self.until = Some(until);
+        self
+    }
+
+    pub fn matches(&self, metadata: &Metadata) -> bool {
+        self.kind.as_ref().map_or(true, |kind| *kind == metadata.kind)
+            && self.device.as_ref().map_or(true, |device| *device == metadata.device)
+            && self.since.map_or(true, |since| metadata.timestamp >= since)
+            && self.until.map_or(true, |until| metadata.timestamp < until)
+    }
+}
+
+/// Lists the records of a channel from their public metadata. Reading needs no keys: records are listed
+/// from their index entries, and from the packets themselves where the reader has access to them
+pub struct MetadataReader<T> {
+    reader: Subscriber<T>,
+    entries: Vec<Entry>,
+}
+
+impl<T: Transport> MetadataReader<T> {
+    /// A reader that is not subscribed to the channel
+    pub async fn new(transport: T, announcement_link: &Address) -> Result<Self> {
+        let mut reader = Subscriber::new("MetadataReader", transport);
+        reader.receive_announcement(announcement_link).await?;
+        Ok(Self::with_subscriber(reader))
+    }
+
+    /// A reader listing records as the subscriber
+    pub fn with_subscriber(reader: Subscriber<T>) -> Self {
+        MetadataReader {
+            reader,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Fetch the messages published since the last refresh and list their records. Returns the number
+    /// of records listed for the first time
+    pub async fn refresh(&mut self) -> usize {
+        let retrieved = self.reader.fetch_all_next_msgs().await;
+        let listed = self.entries.len();
+        for msg in &retrieved {
+            self.list(msg);
+        }
+        self.entries.len() - listed
+    }
+
+    fn list(&mut self, msg: &UnwrappedMessage) {
+        let publisher = match &msg.body {
+            MessageContent::SignedPacket { pk, .. } => PublicKey::from_bytes(pk.as_bytes()).ok(),
+            _ => None,
+        };
+        let (metadata, data_link, index_link) = if let Ok(entry) = decode_public::<IndexEntry>(msg) {
+            (entry.metadata, entry.data_link, Some(msg.link.to_string()))
+        } else if let Ok(metadata) = decode_public::<Metadata>(msg) {
+            (metadata, msg.link.to_string(), None)
+        } else {
+            return;
+        };
+
+        // A reader with access to the data packet lists the record from both the packet and its entry.
+        // The packet takes precedence, and only an entry from the signer of the packet is kept
+        match self.entries.iter_mut().find(|entry| entry.data_link == data_link) {
+            Some(entry) => match index_link {
+                Some(index_link) => {
+                    if entry.from_packet && entry.index_link.is_none() && entry.publisher == publisher {
+                        entry.index_link = Some(index_link);
+                    }
+                }
+                None => {
+                    if entry.publisher != publisher {
+                        entry.index_link = None;
+                    }
+                    entry.metadata = metadata;
+                    entry.publisher = publisher;
+                    entry.from_packet = true;
+                }
+            },
+            None => self.entries.push(Entry {
+                metadata,
+                data_link,
+                from_packet: index_link.is_none(),
+                index_link,
+                publisher,
+            }),
+        }
+    }
+
+    /// Every record listed so far, in the order they were retrieved
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    pub fn filter(&self, filter: &Filter) -> Vec<&Entry> {
+        self.entries.iter().filter(|entry| filter.matches(&entry.metadata)).collect()
+    }
+
+    pub fn into_subscriber(self) -> Subscriber<T> {
+        self.reader
+    }
+}
@@ -0,0 +1,6 @@
+pub mod codec;
+pub mod compression;
+pub mod metadata;
+pub use codec::{decode_masked, decode_public, tag, Codec, CodecError, Format, Record, Tag};
+pub use compression::{compress, decompress, send_compressed, CompressionError, Encoding, MAX_DECOMPRESSED};
+pub use metadata::{Filter, Metadata, MetadataReader};
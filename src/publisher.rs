@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use iota_streams::{
+    app_channels::api::tangle::{Address, Author, Bytes, Subscriber, Transport},
+    core::Result,
+};
+
+/// Anyone able to publish packets in a channel, i.e. the author or a subscriber
+#[async_trait(?Send)]
+pub trait Publisher {
+    /// Send a signed packet linked to `link_to`, returning its link and the link of its sequencing
+    /// message if one was sent
+    async fn send_signed_packet(
+        &mut self,
+        link_to: &Address,
+        public_payload: &Bytes,
+        masked_payload: &Bytes,
+    ) -> Result<(Address, Option<Address>)>;
+
+    async fn send_tagged_packet(
+        &mut self,
+        link_to: &Address,
+        public_payload: &Bytes,
+        masked_payload: &Bytes,
+    ) -> Result<(Address, Option<Address>)>;
+}
+
+#[async_trait(?Send)]
+impl<T: Transport> Publisher for Author<T> {
+    async fn send_signed_packet(
+        &mut self,
+        link_to: &Address,
+        public_payload: &Bytes,
+        masked_payload: &Bytes,
+    ) -> Result<(Address, Option<Address>)> {
+        Author::send_signed_packet(self, link_to, public_payload, masked_payload).await
+    }
+
+    async fn send_tagged_packet(
+        &mut self,
+        link_to: &Address,
+        public_payload: &Bytes,
+        masked_payload: &Bytes,
+    ) -> Result<(Address, Option<Address>)> {
+        Author::send_tagged_packet(self, link_to, public_payload, masked_payload).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<T: Transport> Publisher for Subscriber<T> {
+    async fn send_signed_packet(
+        &mut self,
+        link_to: &Address,
+        public_payload: &Bytes,
+        masked_payload: &Bytes,
+    ) -> Result<(Address, Option<Address>)> {
+        Subscriber::send_signed_packet(self, link_to, public_payload, masked_payload).await
+    }
+
+    async fn send_tagged_packet(
+        &mut self,
+        link_to: &Address,
+        public_payload: &Bytes,
+        masked_payload: &Bytes,
+    ) -> Result<(Address, Option<Address>)> {
+        Subscriber::send_tagged_packet(self, link_to, public_payload, masked_payload).await
+    }
+}
@@ -0,0 +1,223 @@
+use crate::payload::{decode_masked, decode_public, decompress, Record};
+use iota_streams::app_channels::api::tangle::{Address, Bytes, MessageContent, PublicKey, UnwrappedMessage};
+use std::{collections::HashMap, ops::Not, rc::Rc};
+
+/// Kind of a fetched message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Keyload,
+    SignedPacket,
+    TaggedPacket,
+    /// A message the reader could not unwrap
+    Unreadable,
+    Other,
+}
+
+impl Kind {
+    pub fn of(content: &MessageContent) -> Self {
+        match content {
+            MessageContent::Keyload => Kind::Keyload,
+            MessageContent::SignedPacket { .. } => Kind::SignedPacket,
+            MessageContent::TaggedPacket { .. } => Kind::TaggedPacket,
+            MessageContent::Unreadable(_) => Kind::Unreadable,
+            _ => Kind::Other,
+        }
+    }
+}
+
+// Where a message sits in the collection being queried
+struct Position<'a> {
+    index: usize,
+    // Link of the last keyload retrieved before the message
+    keyload: Option<&'a Address>,
+    links: &'a HashMap<String, usize>,
+}
+
+type Predicate = Rc<dyn Fn(&UnwrappedMessage, &Position) -> bool>;
+
+/// A condition on fetched messages, built from the constructors below and combined with [`Query::and`],
+/// [`Query::or`] and `!`. Conditions on ancestry and link ranges are evaluated against the order the
+/// messages were retrieved in, as returned by `fetch_all_next_msgs`
+#[derive(Clone)]
+pub struct Query(Predicate);
+
+impl Query {
+    fn new<F: Fn(&UnwrappedMessage, &Position) -> bool + 'static>(predicate: F) -> Self {
+        Query(Rc::new(predicate))
+    }
+
+    /// Every message
+    pub fn all() -> Self {
+        Self::new(|_, _| true)
+    }
+
+    pub fn kind(kind: Kind) -> Self {
+        Self::new(move |msg, _| Kind::of(&msg.body) == kind)
+    }
+
+    /// Signed and tagged packets the reader could unwrap
+    pub fn packets() -> Self {
+        Self::kind(Kind::SignedPacket).or(Self::kind(Kind::TaggedPacket))
+    }
+
+    /// Signed packets signed by the publisher
+    pub fn publisher(publisher: &PublicKey) -> Self {
+        let publisher = publisher.as_bytes().to_vec();
+        Self::new(move |msg, _| match &msg.body {
+            MessageContent::SignedPacket { pk, .. } => pk.as_bytes()[..] == publisher[..],
+            _ => false,
+        })
+    }
+
+    /// Messages retrieved after the keyload and before the next one, i.e. in the branch it opened
+    pub fn in_branch(keyload: &Address) -> Self {
+        let keyload = keyload.clone();
+        Self::new(move |msg, position| msg.link != keyload && position.keyload == Some(&keyload))
+    }
+
+    /// Messages retrieved before any keyload
+    pub fn before_keyloads() -> Self {
+        Self::new(|msg, position| position.keyload.is_none() && Kind::of(&msg.body) != Kind::Keyload)
+    }
+
+    /// Messages retrieved after the message with the link. Nothing matches if it was not retrieved
+    pub fn after(link: &Address) -> Self {
+        let link = link.to_string();
+        Self::new(move |_, position| position.links.get(&link).map_or(false, |start| position.index > *start))
+    }
+
+    /// Messages retrieved before the message with the link. Everything matches if it was not retrieved
+    pub fn before(link: &Address) -> Self {
+        let link = link.to_string();
+        Self::new(move |_, position| position.links.get(&link).map_or(true, |end| position.index < *end))
+    }
+
+    /// Messages retrieved from the first link up to the second, both included
+    pub fn between(from: &Address, to: &Address) -> Self {
+        Self::after(from)
+            .or(Self::link(from))
+            .and(Self::before(to).or(Self::link(to)))
+    }
+
+    pub fn link(link: &Address) -> Self {
+        let link = link.clone();
+        Self::new(move |msg, _| msg.link == link)
+    }
+
+    /// Packets whose public payload, decompressed if need be, satisfies the predicate
+    pub fn public<F: Fn(&Bytes) -> bool + 'static>(predicate: F) -> Self {
+        Self::new(move |msg, _| payloads(msg).map_or(false, |(public, _)| predicate(&public)))
+    }
+
+    /// Packets whose masked payload, decompressed if need be, satisfies the predicate
+    pub fn masked<F: Fn(&Bytes) -> bool + 'static>(predicate: F) -> Self {
+        Self::new(move |msg, _| payloads(msg).map_or(false, |(_, masked)| predicate(&masked)))
+    }
+
+    /// Packets whose masked payload is the text
+    pub fn masked_text(text: &str) -> Self {
+        let text = text.as_bytes().to_vec();
+        Self::masked(move |masked| masked.0 == text)
+    }
+
+    /// Packets whose public payload decodes into a record satisfying the predicate
+    pub fn public_record<R: Record, F: Fn(&R) -> bool + 'static>(predicate: F) -> Self {
+        Self::new(move |msg, _| decode_public::<R>(msg).map_or(false, |record| predicate(&record)))
+    }
+
+    /// Packets whose masked payload decodes into a record satisfying the predicate
+    pub fn masked_record<R: Record, F: Fn(&R) -> bool + 'static>(predicate: F) -> Self {
+        Self::new(move |msg, _| decode_masked::<R>(msg).map_or(false, |record| predicate(&record)))
+    }
+
+    pub fn and(self, other: Query) -> Self {
+        Self::new(move |msg, position| (self.0)(msg, position) && (other.0)(msg, position))
+    }
+
+    pub fn or(self, other: Query) -> Self {
+        Self::new(move |msg, position| (self.0)(msg, position) || (other.0)(msg, position))
+    }
+
+    /// References to the matching messages, in the order they were retrieved
+    pub fn run<'a>(&self, retrieved: &'a [UnwrappedMessage]) -> Vec<&'a UnwrappedMessage> {
+        let matches = self.matches(retrieved);
+        retrieved
+            .iter()
+            .zip(matches)
+            .filter(|(_, matched)| *matched)
+            .map(|(msg, _)| msg)
+            .collect()
+    }
+
+    /// The matching messages, in the order they were retrieved
+    pub fn select(&self, retrieved: Vec<UnwrappedMessage>) -> Vec<UnwrappedMessage> {
+        let matches = self.matches(&retrieved);
+        retrieved
+            .into_iter()
+            .zip(matches)
+            .filter(|(_, matched)| *matched)
+            .map(|(msg, _)| msg)
+            .collect()
+    }
+
+    /// Split the messages into those that match and those that do not
+    pub fn partition(&self, retrieved: Vec<UnwrappedMessage>) -> (Vec<UnwrappedMessage>, Vec<UnwrappedMessage>) {
+        let matches = self.matches(&retrieved);
+        let (matched, unmatched): (Vec<_>, Vec<_>) = retrieved.into_iter().zip(matches).partition(|(_, matched)| *matched);
+        (
+            matched.into_iter().map(|(msg, _)| msg).collect(),
+            unmatched.into_iter().map(|(msg, _)| msg).collect(),
+        )
+    }
+
+    pub fn count(&self, retrieved: &[UnwrappedMessage]) -> usize {
+        self.matches(retrieved).into_iter().filter(|matched| *matched).count()
+    }
+
+    fn matches(&self, retrieved: &[UnwrappedMessage]) -> Vec<bool> {
+        let links: HashMap<String, usize> = retrieved
+            .iter()
+            .enumerate()
+            .map(|(index, msg)| (msg.link.to_string(), index))
+            .collect();
+        let mut keyload = None;
+        retrieved
+            .iter()
+            .enumerate()
+            .map(|(index, msg)| {
+                if Kind::of(&msg.body) == Kind::Keyload {
+                    keyload = Some(&msg.link);
+                }
+                let position = Position {
+                    index,
+                    keyload,
+                    links: &links,
+                };
+                (self.0)(msg, &position)
+            })
+            .collect()
+    }
+}
+
+impl Not for Query {
+    type Output = Query;
+
+    fn not(self) -> Query {
+        Self::new(move |msg, position| !(self.0)(msg, position))
+    }
+}
+
+fn payloads(msg: &UnwrappedMessage) -> Option<(Bytes, Bytes)> {
+    match &msg.body {
+        MessageContent::SignedPacket {
+            public_payload,
+            masked_payload,
+            ..
+        }
+        | MessageContent::TaggedPacket {
+            public_payload,
+            masked_payload,
+        } => decompress(public_payload, masked_payload).ok(),
+        _ => None,
+    }
+}
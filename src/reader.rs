@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use iota_streams::app_channels::api::tangle::{Author, Subscriber, Transport, UnwrappedMessage};
+
+/// Anyone able to read a channel, i.e. the author or a subscriber
+#[async_trait(?Send)]
+pub trait Reader {
+    /// Fetch the next message of every known publisher, if they sent one
+    async fn fetch_next_msgs(&mut self) -> Vec<UnwrappedMessage>;
+}
+
+#[async_trait(?Send)]
+impl<T: Transport> Reader for Author<T> {
+    async fn fetch_next_msgs(&mut self) -> Vec<UnwrappedMessage> {
+        Author::fetch_next_msgs(self).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<T: Transport> Reader for Subscriber<T> {
+    async fn fetch_next_msgs(&mut self) -> Vec<UnwrappedMessage> {
+        Subscriber::fetch_next_msgs(self).await
+    }
+}
@@ -0,0 +1,409 @@
+use crate::examples::{self, ExampleReport, SeedProvider};
+use futures::FutureExt;
+use iota_streams::{app_channels::api::tangle::Transport, core::Result};
+use serde_json::{json, Value};
+use std::{
+    collections::VecDeque,
+    fmt,
+    panic::AssertUnwindSafe,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use tokio::{runtime, sync::mpsc};
+
+/// The sections the examples are grouped into
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Section {
+    SinglePublisher,
+    MultiPublisher,
+    Utility,
+}
+
+impl Section {
+    pub const ALL: [Section; 3] = [Section::SinglePublisher, Section::MultiPublisher, Section::Utility];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Section::SinglePublisher => "single_publisher",
+            Section::MultiPublisher => "multi_publisher",
+            Section::Utility => "utility",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Section::SinglePublisher => "Single Publisher Examples",
+            Section::MultiPublisher => "Multiple Publisher Examples",
+            Section::Utility => "Utility Examples",
+        }
+    }
+}
+
+/// Every example available to the runner, in the order they are run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Example {
+    SingleBranchPublic,
+    SingleBranchPrivate,
+    SingleDepthPublic,
+    SingleDepthPrivate,
+    MultiBranchMixedPrivacy,
+    SinglePubPerBranch,
+    MultiPubPerBranch,
+    FetchPrev,
+    GrantAndRevokeAccess,
+    PkKeyloads,
+    PskKeyloads,
+    StateRecovery,
+    StatelessRecovery,
+    NetworkFaults,
+    TaggedPackets,
+}
+
+impl Example {
+    pub const ALL: [Example; 15] = [
+        Example::SingleBranchPublic,
+        Example::SingleBranchPrivate,
+        Example::SingleDepthPublic,
+        Example::SingleDepthPrivate,
+        Example::MultiBranchMixedPrivacy,
+        Example::SinglePubPerBranch,
+        Example::MultiPubPerBranch,
+        Example::FetchPrev,
+        Example::GrantAndRevokeAccess,
+        Example::PkKeyloads,
+        Example::PskKeyloads,
+        Example::StateRecovery,
+        Example::StatelessRecovery,
+        Example::NetworkFaults,
+        Example::TaggedPackets,
+    ];
+
+    /// Name of the example module, used to select the example from the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            Example::SingleBranchPublic => "single_branch_public",
+            Example::SingleBranchPrivate => "single_branch_private",
+            Example::SingleDepthPublic => "single_depth_public",
+            Example::SingleDepthPrivate => "single_depth_private",
+            Example::MultiBranchMixedPrivacy => "multi_branch_mixed_privacy",
+            Example::SinglePubPerBranch => "single_pub_per_branch",
+            Example::MultiPubPerBranch => "multi_pub_per_branch",
+            Example::FetchPrev => "fetch_prev",
+            Example::GrantAndRevokeAccess => "grant_and_revoke_access",
+            Example::PkKeyloads => "pk_keyloads",
+            Example::PskKeyloads => "psk_keyloads",
+            Example::StateRecovery => "state_recovery",
+            Example::StatelessRecovery => "stateless_recovery",
+            Example::NetworkFaults => "network_faults",
+            Example::TaggedPackets => "tagged_packets",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Example::SingleBranchPublic => "Public - Single Branch - Single Publisher",
+            Example::SingleBranchPrivate => "Private - Single Branch - Single Publisher",
+            Example::SingleDepthPublic => "Public - Single Depth - Single Publisher",
+            Example::SingleDepthPrivate => "Private - Single Depth - Single Publisher",
+            Example::MultiBranchMixedPrivacy => "Mixed - Multi Branch - Single Publisher",
+            Example::SinglePubPerBranch => "Private - Multi Branch - Single Publisher per Branch",
+            Example::MultiPubPerBranch => "Private - Multi Branch - Multiple Publishers per Branch",
+            Example::FetchPrev => "Previous Message Retrieval",
+            Example::GrantAndRevokeAccess => "Granting and Revoking Access",
+            Example::PkKeyloads => "Using Public Keys for Keyload Generation",
+            Example::PskKeyloads => "Using Pre Shared Keys for Keyload Generation",
+            Example::StateRecovery => "State Recovery",
+            Example::StatelessRecovery => "Stateless Recovery",
+            Example::NetworkFaults => "Sequencing and Retrieval Under Network Faults",
+            Example::TaggedPackets => "Tagged and Signed Packets",
+        }
+    }
+
+    pub fn section(&self) -> Section {
+        match self {
+            Example::SingleBranchPublic
+            | Example::SingleBranchPrivate
+            | Example::SingleDepthPublic
+            | Example::SingleDepthPrivate
+            | Example::MultiBranchMixedPrivacy => Section::SinglePublisher,
+            Example::SinglePubPerBranch | Example::MultiPubPerBranch => Section::MultiPublisher,
+            Example::FetchPrev
+            | Example::GrantAndRevokeAccess
+            | Example::PkKeyloads
+            | Example::PskKeyloads
+            | Example::StateRecovery
+            | Example::StatelessRecovery
+            | Example::NetworkFaults
+            | Example::TaggedPackets => Section::Utility,
+        }
+    }
+
+    /// Run the example. `dir` is where the example may write any files, i.e. exported user states
+    pub async fn run<T: Transport>(&self, transport: T, seeds: &mut SeedProvider, dir: &Path) -> Result<ExampleReport> {
+        match self {
+            Example::SingleBranchPublic => examples::single_branch_public::example(transport, seeds).await,
+            Example::SingleBranchPrivate => examples::single_branch_private::example(transport, seeds).await,
+            Example::SingleDepthPublic => examples::single_depth_public::example(transport, seeds).await,
+            Example::SingleDepthPrivate => examples::single_depth_private::example(transport, seeds).await,
+            Example::MultiBranchMixedPrivacy => examples::multi_branch_mixed_privacy::example(transport, seeds).await,
+            Example::SinglePubPerBranch => examples::single_pub_per_branch::example(transport, seeds).await,
+            Example::MultiPubPerBranch => examples::multi_pub_per_branch::example(transport, seeds).await,
+            Example::FetchPrev => examples::fetch_prev::example(transport, seeds).await,
+            Example::GrantAndRevokeAccess => examples::grant_and_revoke_access::example(transport, seeds).await,
+            Example::PkKeyloads => examples::pk_keyloads::example(transport, seeds).await,
+            Example::PskKeyloads => examples::psk_keyloads::example(transport, seeds).await,
+            Example::StateRecovery => examples::state_recovery::example(transport, seeds, dir).await,
+            Example::StatelessRecovery => examples::stateless_recovery::example(transport, seeds).await,
+            Example::NetworkFaults => examples::network_faults::example(transport, seeds).await,
+            Example::TaggedPackets => examples::tagged_packets::example(transport, seeds).await,
+        }
+    }
+}
+
+/// Resolve a list of example and section names into the examples they refer to. An empty
+/// selection refers to all examples
+pub fn select(names: &[String]) -> Result<Vec<Example>> {
+    if names.is_empty() {
+        return Ok(Example::ALL.to_vec());
+    }
+
+    let mut selected = Vec::new();
+    for name in names {
+        let matches: Vec<Example> = match Section::ALL.iter().find(|s| s.name() == name) {
+            Some(section) => Example::ALL.iter().copied().filter(|e| e.section() == *section).collect(),
+            None => Example::ALL.iter().copied().filter(|e| e.name() == name).collect(),
+        };
+        if matches.is_empty() {
+            anyhow::bail!("Unknown example or section: {}", name);
+        }
+        for example in matches {
+            if !selected.contains(&example) {
+                selected.push(example);
+            }
+        }
+    }
+    Ok(selected)
+}
+
+/// Outcome of a single example run, with the report of the example if it completed
+pub struct RunResult {
+    pub example: Example,
+    pub duration: Duration,
+    pub error: Option<String>,
+    pub report: Option<ExampleReport>,
+    /// Directory the example ran in, removed once the run completed
+    pub dir: Option<PathBuf>,
+}
+
+impl RunResult {
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "example": self.example.name(),
+            "section": self.example.section().name(),
+            "passed": self.passed(),
+            "duration_ms": self.duration.as_secs_f64() * 1000.0,
+            "error": self.error,
+            "report": self.report,
+        })
+    }
+}
+
+/// JSON document of a set of example runs, suitable for dashboards or diffing between runs
+pub fn to_json(results: &[RunResult], seed: Option<u64>) -> Value {
+    json!({
+        "seed": seed,
+        "passed": results.iter().filter(|r| r.passed()).count(),
+        "failed": results.iter().filter(|r| !r.passed()).count(),
+        "results": results.iter().map(RunResult::to_json).collect::<Vec<_>>(),
+    })
+}
+
+/// Directory holding the files of a single example run, removed along with its contents on drop
+pub struct RunDir(PathBuf);
+
+impl RunDir {
+    pub fn new(name: &str) -> Result<Self> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "streams-examples-{}-{}-{}",
+            std::process::id(),
+            name,
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&path)?;
+        Ok(RunDir(path))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for RunDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Run the provided examples on `jobs` worker threads, each example with its own transport, run
+/// directory and seeds derived from the provided seeds. Transports are not `Send`, so every worker
+/// creates the transports of its examples and drives them on a runtime of its own. Failures (including
+/// panics from failed verifications) are recorded and do not stop the remaining examples. Results are
+/// printed and returned in the order the examples were selected
+pub async fn run_all<T, F>(selected: &[Example], new_transport: F, seeds: &SeedProvider, jobs: usize) -> Vec<RunResult>
+where
+    T: Transport + 'static,
+    F: Fn() -> T + Send + Sync + 'static,
+{
+    let queue: VecDeque<_> = selected
+        .iter()
+        .enumerate()
+        .map(|(index, example)| (index, *example, seeds.for_example(example.name())))
+        .collect();
+    let queue = Arc::new(Mutex::new(queue));
+    let new_transport = Arc::new(new_transport);
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    for _ in 0..jobs.max(1).min(selected.len()) {
+        let queue = queue.clone();
+        let new_transport = new_transport.clone();
+        let sender = sender.clone();
+        thread::spawn(move || {
+            let runtime = runtime::Builder::new_current_thread().enable_all().build();
+            loop {
+                let next = queue.lock().map(|mut queue| queue.pop_front()).unwrap_or(None);
+                let (index, example, seeds) = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+                let result = match &runtime {
+                    Ok(runtime) => runtime.block_on(async { run_one(example, new_transport(), seeds).await }),
+                    Err(e) => failed(example, e.to_string()),
+                };
+                if sender.send((index, result)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(sender);
+
+    let mut results: Vec<Option<RunResult>> = selected.iter().map(|_| None).collect();
+    while let Some((index, result)) = receiver.recv().await {
+        results[index] = Some(result);
+    }
+
+    let mut section = None;
+    selected
+        .iter()
+        .zip(results)
+        .map(|(example, result)| {
+            let result =
+                result.unwrap_or_else(|| failed(*example, "Worker stopped before running the example".to_string()));
+            if section != Some(example.section()) {
+                section = Some(example.section());
+                println!("\n---------------------------------------");
+                println!("{}", example.section().title());
+            }
+            println!("\n---------------------------------------");
+            println!("\n{}\n", example.title());
+            if let Some(report) = &result.report {
+                print!("{}", report);
+            }
+            if let Some(e) = &result.error {
+                println!("\nExample failed: {}", e);
+            }
+            result
+        })
+        .collect()
+}
+
+fn failed(example: Example, error: String) -> RunResult {
+    RunResult {
+        example,
+        duration: Duration::default(),
+        error: Some(error),
+        report: None,
+        dir: None,
+    }
+}
+
+async fn run_one<T: Transport>(example: Example, transport: T, mut seeds: SeedProvider) -> RunResult {
+    let start = Instant::now();
+    let mut run_dir = None;
+    let outcome = match RunDir::new(example.name()) {
+        Ok(dir) => {
+            run_dir = Some(dir.path().to_path_buf());
+            AssertUnwindSafe(example.run(transport, &mut seeds, dir.path()))
+                .catch_unwind()
+                .await
+        }
+        Err(e) => Ok(Err(e)),
+    };
+    let (report, error) = match outcome {
+        Ok(Ok(report)) => (Some(report), None),
+        Ok(Err(e)) => (None, Some(e.to_string())),
+        Err(panic) => (None, Some(panic_message(panic))),
+    };
+
+    RunResult {
+        example,
+        duration: start.elapsed(),
+        error,
+        report,
+        dir: run_dir,
+    }
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(msg) => format!("panicked: {}", msg),
+        Err(panic) => match panic.downcast::<&str>() {
+            Ok(msg) => format!("panicked: {}", msg),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+/// Pass/fail table of a set of example runs
+pub struct Summary<'a>(pub &'a [RunResult]);
+
+impl fmt::Display for Summary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.0.iter().map(|r| r.example.name().len()).max().unwrap_or(0).max(7);
+        writeln!(f, "{:<width$}  {:<6}  {:>10}", "Example", "Result", "Duration", width = width)?;
+        writeln!(f, "{}", "-".repeat(width + 20))?;
+        for result in self.0 {
+            writeln!(
+                f,
+                "{:<width$}  {:<6}  {:>9.2}s",
+                result.example.name(),
+                if result.passed() { "PASS" } else { "FAIL" },
+                result.duration.as_secs_f64(),
+                width = width
+            )?;
+        }
+        let passed = self.0.iter().filter(|r| r.passed()).count();
+        let total: Duration = self.0.iter().map(|r| r.duration).sum();
+        writeln!(f, "{}", "-".repeat(width + 20))?;
+        write!(
+            f,
+            "{} passed, {} failed in {:.2}s",
+            passed,
+            self.0.len() - passed,
+            total.as_secs_f64()
+        )?;
+        for result in self.0.iter().filter(|r| !r.passed()) {
+            write!(f, "\n\t{}: {}", result.example.name(), result.error.as_deref().unwrap_or_default())?;
+        }
+        Ok(())
+    }
+}
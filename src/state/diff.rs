@@ -0,0 +1,186 @@
+use crate::state::{PublisherState, StateSummary};
+use iota_streams::core::Result;
+use serde::Serialize;
+use std::{cmp::Ordering, collections::BTreeMap, fmt};
+
+/// Which of two snapshots is further along the sequences of the publishers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Ahead {
+    /// Neither, the snapshots are at the same position for every publisher
+    Neither,
+    First,
+    Second,
+    /// Each snapshot has seen something the other has not, e.g. two processes published from the same
+    /// identity after restoring the same snapshot
+    Diverged,
+}
+
+/// The sequence states of a publisher in both snapshots. A missing state means the snapshot does not
+/// know the publisher
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SequenceChange {
+    pub public_key: String,
+    pub first: Option<PublisherState>,
+    pub second: Option<PublisherState>,
+}
+
+impl SequenceChange {
+    // Later branches are ahead of earlier ones, then later sequence numbers
+    fn ordering(&self) -> Ordering {
+        let position = |state: &Option<PublisherState>| state.as_ref().map(|s| (s.branch_no, s.seq_no));
+        position(&self.first).cmp(&position(&self.second))
+    }
+}
+
+/// Differences between two snapshots of the same identity, going from the first to the second
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StateDiff {
+    pub ahead: Ahead,
+    /// Publishers whose sequence state differs
+    pub publishers: Vec<SequenceChange>,
+    /// Subscribers and PSK ids only in one of the snapshots. They do not count towards `ahead`, as
+    /// removing one does not move a snapshot forward
+    pub subscribers_added: Vec<String>,
+    pub subscribers_removed: Vec<String>,
+    pub psk_ids_added: Vec<String>,
+    pub psk_ids_removed: Vec<String>,
+}
+
+impl StateDiff {
+    pub fn is_empty(&self) -> bool {
+        self.publishers.is_empty()
+            && self.subscribers_added.is_empty()
+            && self.subscribers_removed.is_empty()
+            && self.psk_ids_added.is_empty()
+            && self.psk_ids_removed.is_empty()
+    }
+}
+
+impl fmt::Display for StateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ahead = match self.ahead {
+            Ahead::Neither if self.is_empty() => "Snapshots are identical",
+            Ahead::Neither => "Neither snapshot is ahead",
+            Ahead::First => "First snapshot is ahead",
+            Ahead::Second => "Second snapshot is ahead",
+            Ahead::Diverged => "Snapshots have diverged",
+        };
+        writeln!(f, "{}", ahead)?;
+        let position = |state: &Option<PublisherState>| match state {
+            Some(state) => format!("branch {} seq {}", state.branch_no, state.seq_no),
+            None => "unknown".to_string(),
+        };
+        for change in &self.publishers {
+            writeln!(
+                f,
+                "Publisher {}: {} -> {}",
+                change.public_key,
+                position(&change.first),
+                position(&change.second)
+            )?;
+        }
+        for subscriber in &self.subscribers_added {
+            writeln!(f, "+ subscriber {}", subscriber)?;
+        }
+        for subscriber in &self.subscribers_removed {
+            writeln!(f, "- subscriber {}", subscriber)?;
+        }
+        for pskid in &self.psk_ids_added {
+            writeln!(f, "+ psk id {}", pskid)?;
+        }
+        for pskid in &self.psk_ids_removed {
+            writeln!(f, "- psk id {}", pskid)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare two snapshots of the same identity. Fails if they were exported by users of different roles
+/// or public keys, or belong to different channels.
+///
+/// A snapshot is ahead when it is further along the sequence of some publisher, or knows a publisher the
+/// other does not. Snapshots that are each ahead somewhere have diverged
+pub fn diff(first: &StateSummary, second: &StateSummary) -> Result<StateDiff> {
+    anyhow::ensure!(
+        first.role == second.role,
+        "Snapshots were exported by different roles: {:?} and {:?}",
+        first.role,
+        second.role
+    );
+    anyhow::ensure!(
+        first.public_key == second.public_key,
+        "Snapshots belong to different users: public keys {} and {}",
+        first.public_key,
+        second.public_key
+    );
+    // A snapshot taken before the announcement does not know the channel yet
+    if let (Some(first_link), Some(second_link)) = (&first.announcement_link, &second.announcement_link) {
+        anyhow::ensure!(
+            first_link == second_link,
+            "Snapshots belong to different channels: announcement links {} and {}",
+            first_link,
+            second_link
+        );
+    }
+
+    let mut changes: BTreeMap<&str, SequenceChange> = BTreeMap::new();
+    for (state, in_first) in first
+        .publishers
+        .iter()
+        .map(|state| (state, true))
+        .chain(second.publishers.iter().map(|state| (state, false)))
+    {
+        let change = changes.entry(state.public_key.as_str()).or_insert_with(|| SequenceChange {
+            public_key: state.public_key.clone(),
+            first: None,
+            second: None,
+        });
+        if in_first {
+            change.first = Some(state.clone());
+        } else {
+            change.second = Some(state.clone());
+        }
+    }
+    let publishers: Vec<SequenceChange> = changes
+        .into_values()
+        .filter(|change| change.first != change.second)
+        .collect();
+
+    let (subscribers_added, subscribers_removed) = compare(&first.subscribers, &second.subscribers);
+    let (psk_ids_added, psk_ids_removed) = compare(&first.psk_ids, &second.psk_ids);
+
+    let (mut first_ahead, mut second_ahead) = (false, false);
+    for change in &publishers {
+        match change.ordering() {
+            Ordering::Greater => first_ahead = true,
+            Ordering::Less => second_ahead = true,
+            // Same position but a different last link: both published from it
+            Ordering::Equal => {
+                first_ahead = true;
+                second_ahead = true;
+            }
+        }
+    }
+    let ahead = match (first_ahead, second_ahead) {
+        (false, false) => Ahead::Neither,
+        (true, false) => Ahead::First,
+        (false, true) => Ahead::Second,
+        (true, true) => Ahead::Diverged,
+    };
+
+    Ok(StateDiff {
+        ahead,
+        publishers,
+        subscribers_added,
+        subscribers_removed,
+        psk_ids_added,
+        psk_ids_removed,
+    })
+}
+
+// Entries only in the second list, and entries only in the first
+fn compare(first: &[String], second: &[String]) -> (Vec<String>, Vec<String>) {
+    let added = second.iter().filter(|id| !first.contains(id)).cloned().collect();
+    let removed = first.iter().filter(|id| !second.contains(id)).cloned().collect();
+    (added, removed)
+}
@@ -0,0 +1,211 @@
+use crate::{examples::report::channel_type_name, state::Password};
+use iota_streams::{
+    app::identifier::Identifier,
+    app_channels::api::tangle::{Author, ChannelType, PublicKey, Subscriber, Transport},
+    core::{
+        psk::{PskId, PSKID_SIZE},
+        Result,
+    },
+};
+use serde::Serialize;
+use std::{fmt, str::FromStr};
+
+/// Kind of user a state was exported by
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Role {
+    Author,
+    Subscriber,
+}
+
+impl FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(role: &str) -> Result<Self> {
+        match role {
+            "author" => Ok(Role::Author),
+            "subscriber" => Ok(Role::Subscriber),
+            other => anyhow::bail!("Unknown role: {}", other),
+        }
+    }
+}
+
+/// Where a publisher is in its sequence: the link of the last message it sent, the branch and the
+/// sequence number of its next message
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PublisherState {
+    pub public_key: String,
+    pub link: String,
+    pub branch_no: u32,
+    pub seq_no: u32,
+}
+
+/// What an exported state holds. Keys and ids are hex encoded, publishers and keys are sorted so that
+/// summaries of the same state always compare equal
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StateSummary {
+    pub role: Role,
+    pub public_key: String,
+    /// Not known to an author before it announces the channel, nor to a subscriber before it receives the
+    /// announcement
+    pub announcement_link: Option<String>,
+    /// "SingleBranch", "MultiBranch" or "SingleDepth"
+    pub channel_type: Option<String>,
+    pub author_public_key: Option<String>,
+    pub publishers: Vec<PublisherState>,
+    pub psk_ids: Vec<String>,
+    /// Public keys in the key store of an author other than its own. Empty for subscribers, which do not
+    /// keep track of who else subscribed
+    pub subscribers: Vec<String>,
+}
+
+impl StateSummary {
+    fn new(
+        role: Role,
+        public_key: &PublicKey,
+        author_public_key: Option<&PublicKey>,
+        announcement_link: Option<String>,
+        channel_type: ChannelType,
+        key_store: Vec<(String, PublisherState)>,
+    ) -> Result<Self> {
+        let public_key = hex::encode(public_key.as_bytes());
+        let author_public_key = author_public_key.map(|pk| hex::encode(pk.as_bytes()));
+        let channel_type = announcement_link.as_ref().map(|_| channel_type_name(&channel_type).to_string());
+
+        let mut summary = StateSummary {
+            role,
+            public_key,
+            announcement_link,
+            channel_type,
+            author_public_key,
+            publishers: Vec::new(),
+            psk_ids: Vec::new(),
+            subscribers: Vec::new(),
+        };
+        for (id, state) in key_store {
+            match identifier(&id)? {
+                Identifier::PskId(_) => summary.psk_ids.push(id),
+                Identifier::EdPubKey(_) => {
+                    if role == Role::Author && id != summary.public_key {
+                        summary.subscribers.push(id);
+                    }
+                    summary.publishers.push(state);
+                }
+            }
+        }
+        summary.publishers.sort_by(|a, b| a.public_key.cmp(&b.public_key));
+        summary.psk_ids.sort();
+        summary.subscribers.sort();
+        Ok(summary)
+    }
+}
+
+// Key store entries are listed under the hex encoded bytes of their identifier, parse them back into one
+fn identifier(id: &str) -> Result<Identifier> {
+    let bytes = hex::decode(id)?;
+    if bytes.len() == PSKID_SIZE {
+        return Ok(PskId::clone_from_slice(&bytes).into());
+    }
+    let public_key = PublicKey::from_bytes(&bytes)
+        .map_err(|e| anyhow::anyhow!("Key store entry {} is neither a PSK id nor a public key: {}", id, e))?;
+    Ok(public_key.into())
+}
+
+fn channel_type(multi_branching: bool, single_depth: bool) -> ChannelType {
+    if single_depth {
+        ChannelType::SingleDepth
+    } else if multi_branching {
+        ChannelType::MultiBranch
+    } else {
+        ChannelType::SingleBranch
+    }
+}
+
+impl fmt::Display for StateSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Role: {:?}", self.role)?;
+        writeln!(f, "Public Key: {}", self.public_key)?;
+        writeln!(f, "Announcement Link: {}", self.announcement_link.as_deref().unwrap_or("none"))?;
+        writeln!(f, "Channel Type: {}", self.channel_type.as_deref().unwrap_or("unknown"))?;
+        if let Some(author) = &self.author_public_key {
+            writeln!(f, "Author Public Key: {}", author)?;
+        }
+        writeln!(f, "Publishers: {}", self.publishers.len())?;
+        for publisher in &self.publishers {
+            writeln!(
+                f,
+                "\t{} branch {} seq {} last link {}",
+                publisher.public_key, publisher.branch_no, publisher.seq_no, publisher.link
+            )?;
+        }
+        writeln!(f, "PSK Ids: {}", self.psk_ids.len())?;
+        for pskid in &self.psk_ids {
+            writeln!(f, "\t{}", pskid)?;
+        }
+        writeln!(f, "Subscribers: {}", self.subscribers.len())?;
+        for subscriber in &self.subscribers {
+            writeln!(f, "\t{}", subscriber)?;
+        }
+        Ok(())
+    }
+}
+
+/// Import a state exported by an author and summarise it
+pub async fn inspect_author<T: Transport>(state: &[u8], password: &Password, transport: T) -> Result<StateSummary> {
+    let author = Author::import(state, password.expose(), transport).await?;
+    let key_store = author
+        .fetch_state()?
+        .into_iter()
+        .map(|(id, cursor)| {
+            let state = PublisherState {
+                public_key: id.clone(),
+                link: cursor.link.to_string(),
+                branch_no: cursor.branch_no,
+                seq_no: cursor.seq_no,
+            };
+            (id, state)
+        })
+        .collect();
+    StateSummary::new(
+        Role::Author,
+        author.get_public_key(),
+        Some(author.get_public_key()),
+        author.announcement_link().as_ref().map(|link| link.to_string()),
+        channel_type(author.is_multi_branching(), author.is_single_depth()),
+        key_store,
+    )
+}
+
+/// Import a state exported by a subscriber and summarise it
+pub async fn inspect_subscriber<T: Transport>(state: &[u8], password: &Password, transport: T) -> Result<StateSummary> {
+    let subscriber = Subscriber::import(state, password.expose(), transport).await?;
+    let key_store = subscriber
+        .fetch_state()?
+        .into_iter()
+        .map(|(id, cursor)| {
+            let state = PublisherState {
+                public_key: id.clone(),
+                link: cursor.link.to_string(),
+                branch_no: cursor.branch_no,
+                seq_no: cursor.seq_no,
+            };
+            (id, state)
+        })
+        .collect();
+    StateSummary::new(
+        Role::Subscriber,
+        subscriber.get_public_key(),
+        subscriber.author_public_key(),
+        subscriber.announcement_link().as_ref().map(|link| link.to_string()),
+        channel_type(subscriber.is_multi_branching(), subscriber.is_single_depth()),
+        key_store,
+    )
+}
+
+/// Import a state exported by a user of the role and summarise it. Importing does not touch the
+/// transport, any transport will do
+pub async fn inspect<T: Transport>(role: Role, state: &[u8], password: &Password, transport: T) -> Result<StateSummary> {
+    match role {
+        Role::Author => inspect_author(state, password, transport).await,
+        Role::Subscriber => inspect_subscriber(state, password, transport).await,
+    }
+}
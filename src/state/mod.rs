@@ -0,0 +1,8 @@
+pub mod diff;
+pub mod inspect;
+pub mod secret;
+pub mod store;
+pub use diff::{diff, Ahead, SequenceChange, StateDiff};
+pub use inspect::{inspect, inspect_author, inspect_subscriber, PublisherState, Role, StateSummary};
+pub use secret::{reencrypt, Password, PasswordSource};
+pub use store::{Persist, PersistentUser, StateStore, StoreConfig};
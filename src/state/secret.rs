@@ -0,0 +1,93 @@
+use crate::state::Persist;
+use iota_streams::core::Result;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Environment variable holding the password exported states are encrypted with
+pub const PASSWORD_ENV_VAR: &str = "STREAMS_STATE_PASSWORD";
+
+/// Password of an exported state. It is zeroized when dropped and never shown by `Debug`
+#[derive(Clone)]
+pub struct Password(Zeroizing<String>);
+
+impl Password {
+    pub fn new(password: String) -> Self {
+        Password(Zeroizing::new(password))
+    }
+
+    /// The password, to pass to `export` or `import`. Avoid copying it anywhere that is not zeroized
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Password {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Password(<redacted>)")
+    }
+}
+
+/// Where a password is read from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PasswordSource {
+    /// An environment variable
+    Env(String),
+    /// A file holding the password, without anything but a trailing newline. On unix the file must not
+    /// be accessible by group or others
+    File(PathBuf),
+    /// An interactive prompt on the terminal, with the provided message
+    Prompt(String),
+}
+
+impl PasswordSource {
+    /// The default environment variable
+    pub fn env() -> Self {
+        PasswordSource::Env(PASSWORD_ENV_VAR.to_string())
+    }
+
+    pub fn load(&self) -> Result<Password> {
+        let password = match self {
+            PasswordSource::Env(var) => std::env::var(var)
+                .map_err(|_| anyhow::anyhow!("Environment variable {} is not set", var))?,
+            PasswordSource::File(path) => read_key_file(path)?,
+            PasswordSource::Prompt(message) => rpassword::read_password_from_tty(Some(message))?,
+        };
+        let password = Password::new(password);
+        anyhow::ensure!(!password.expose().is_empty(), "Password from {:?} is empty", self);
+        Ok(password)
+    }
+}
+
+fn read_key_file(path: &Path) -> Result<String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(path)?.permissions().mode();
+        anyhow::ensure!(
+            mode & 0o077 == 0,
+            "Key file {} can be read by others (mode {:o}), restrict it with `chmod 600`",
+            path.display(),
+            mode & 0o777
+        );
+    }
+
+    let mut contents = std::fs::read_to_string(path)?;
+    let password = contents.trim_end_matches(|c| c == '\n' || c == '\r').to_string();
+    contents.zeroize();
+    Ok(password)
+}
+
+/// Encrypt an exported state under a new password. The state is imported with the current password and
+/// exported again, so `U` must be the kind of user that exported it
+pub async fn reencrypt<U: Persist>(
+    state: &[u8],
+    current: &Password,
+    new: &Password,
+    transport: U::Transport,
+) -> Result<Vec<u8>> {
+    let user = U::import(state, current.expose(), transport).await?;
+    user.export(new.expose()).await
+}
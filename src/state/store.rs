@@ -0,0 +1,424 @@
+use crate::{publisher::Publisher, reader::Reader, state::Password};
+use async_trait::async_trait;
+use iota_streams::{
+    app::identifier::Identifier,
+    app_channels::api::tangle::{Address, Author, Bytes, Subscriber, Transport, UnwrappedMessage},
+    core::{
+        psk::{Psk, PskId},
+        Result,
+    },
+};
+use std::{
+    fs,
+    io::Write,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+/// A user whose state can be exported and imported, i.e. the author or a subscriber
+#[async_trait(?Send)]
+pub trait Persist: Sized {
+    type Transport: Clone;
+    async fn export(&self, password: &str) -> Result<Vec<u8>>;
+    async fn import(state: &[u8], password: &str, transport: Self::Transport) -> Result<Self>;
+}
+
+#[async_trait(?Send)]
+impl<T: Transport> Persist for Author<T> {
+    type Transport = T;
+
+    async fn export(&self, password: &str) -> Result<Vec<u8>> {
+        Author::export(self, password).await
+    }
+
+    async fn import(state: &[u8], password: &str, transport: T) -> Result<Self> {
+        Author::import(state, password, transport).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<T: Transport> Persist for Subscriber<T> {
+    type Transport = T;
+
+    async fn export(&self, password: &str) -> Result<Vec<u8>> {
+        Subscriber::export(self, password).await
+    }
+
+    async fn import(state: &[u8], password: &str, transport: T) -> Result<Self> {
+        Subscriber::import(state, password, transport).await
+    }
+}
+
+/// Rotated snapshots of a user state in a directory. Snapshots are named `<name>.<sequence>.state`
+/// and written to a temporary file first, then renamed, so a crash never leaves a partial snapshot
+/// behind under a snapshot name
+#[derive(Clone, Debug)]
+pub struct StateStore {
+    dir: PathBuf,
+    name: String,
+    keep: usize,
+}
+
+impl StateStore {
+    /// A store keeping the `keep` newest snapshots (at least one) of the user called `name`
+    pub fn new<P: AsRef<Path>>(dir: P, name: &str, keep: usize) -> Result<Self> {
+        anyhow::ensure!(
+            !name.is_empty() && !name.contains(|c| c == '.' || c == '/' || c == '\\'),
+            "Invalid state name: {:?}",
+            name
+        );
+        fs::create_dir_all(dir.as_ref())?;
+        let store = StateStore {
+            dir: dir.as_ref().to_path_buf(),
+            name: name.to_string(),
+            keep: keep.max(1),
+        };
+
+        // Temporary files are only left behind by a save that did not complete
+        for entry in fs::read_dir(&store.dir)? {
+            let path = entry?.path();
+            if path.extension().map_or(false, |ext| ext == "tmp") && store.sequence(&path.with_extension("")).is_some() {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(store)
+    }
+
+    fn sequence(&self, path: &Path) -> Option<u64> {
+        let file_name = path.file_name()?.to_str()?;
+        let sequence = file_name.strip_prefix(&self.name)?.strip_prefix('.')?.strip_suffix(".state")?;
+        sequence.parse().ok()
+    }
+
+    /// Paths of the snapshots in the store, newest first
+    pub fn snapshots(&self) -> Result<Vec<PathBuf>> {
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if let Some(sequence) = self.sequence(&path) {
+                snapshots.push((sequence, path));
+            }
+        }
+        snapshots.sort_by(|a, b| b.0.cmp(&a.0));
+        Ok(snapshots.into_iter().map(|(_, path)| path).collect())
+    }
+
+    /// Write a new snapshot and remove the ones beyond the number kept
+    pub fn save(&self, state: &[u8]) -> Result<PathBuf> {
+        let snapshots = self.snapshots()?;
+        let sequence = snapshots.first().and_then(|path| self.sequence(path)).map_or(0, |s| s + 1);
+        let path = self.dir.join(format!("{}.{:020}.state", self.name, sequence));
+        let tmp = path.with_extension("state.tmp");
+
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(state)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&tmp, &path)?;
+
+        for old in snapshots.iter().skip(self.keep - 1) {
+            fs::remove_file(old)?;
+        }
+        Ok(path)
+    }
+}
+
+/// When a [`PersistentUser`] snapshots its state
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StoreConfig {
+    /// Number of sends and receives between snapshots
+    pub every: usize,
+    /// Number of snapshots kept
+    pub keep: usize,
+}
+
+impl Default for StoreConfig {
+    fn default() -> Self {
+        StoreConfig { every: 1, keep: 3 }
+    }
+}
+
+/// An author or subscriber that snapshots its state to a [`StateStore`] after its sends and receives.
+///
+/// Operations that change the state are made through the wrapper for them to be counted, i.e. through
+/// the methods below or the [`Publisher`] and [`Reader`] traits, or through [`PersistentUser::user_mut`]
+/// followed by a snapshot. Read-only access to the user goes through `Deref`.
+///
+/// An operation that succeeds returns its result even if its snapshot could not be written, as the
+/// message it sent is on the tangle either way. The snapshot error is kept for
+/// [`PersistentUser::snapshot_error`] and the snapshot is retried with the next operation.
+pub struct PersistentUser<U> {
+    user: U,
+    store: StateStore,
+    password: Password,
+    every: usize,
+    pending: usize,
+    snapshot_error: Option<anyhow::Error>,
+}
+
+impl<U> PersistentUser<U> {
+    pub fn new(user: U, dir: &Path, name: &str, password: &Password, config: StoreConfig) -> Result<Self> {
+        Ok(Self::with_store(
+            user,
+            StateStore::new(dir, name, config.keep)?,
+            password.clone(),
+            config,
+        ))
+    }
+
+    fn with_store(user: U, store: StateStore, password: Password, config: StoreConfig) -> Self {
+        PersistentUser {
+            user,
+            store,
+            password,
+            every: config.every.max(1),
+            pending: 0,
+            snapshot_error: None,
+        }
+    }
+
+    /// Mutable access to the user, for operations the wrapper does not cover. These are not counted,
+    /// follow them with [`PersistentUser::snapshot`] where they change the state
+    pub fn user_mut(&mut self) -> &mut U {
+        &mut self.user
+    }
+
+    pub fn store(&self) -> &StateStore {
+        &self.store
+    }
+
+    /// Why the last automatic snapshot failed, if it did and none succeeded since. Operations made in
+    /// the meantime are not persisted yet
+    pub fn snapshot_error(&self) -> Option<&anyhow::Error> {
+        self.snapshot_error.as_ref()
+    }
+
+    pub fn into_inner(self) -> U {
+        self.user
+    }
+}
+
+impl<U: Persist> PersistentUser<U> {
+    /// Restore the user from the newest snapshot that imports, skipping corrupt or unreadable ones.
+    /// Returns `None` if there is no snapshot, and fails if there are snapshots but none imports, e.g.
+    /// with a wrong password, rather than letting a new user rotate them away
+    pub async fn restore(
+        dir: &Path,
+        name: &str,
+        password: &Password,
+        transport: U::Transport,
+        config: StoreConfig,
+    ) -> Result<Option<Self>> {
+        let store = StateStore::new(dir, name, config.keep)?;
+        let snapshots = store.snapshots()?;
+        if snapshots.is_empty() {
+            return Ok(None);
+        }
+
+        let (mut unreadable, mut failures) = (0, Vec::new());
+        for snapshot in &snapshots {
+            let state = match fs::read(snapshot) {
+                Ok(state) => state,
+                Err(e) => {
+                    unreadable += 1;
+                    failures.push(format!("{}: unreadable: {}", snapshot.display(), e));
+                    continue;
+                }
+            };
+            match U::import(&state, password.expose(), transport.clone()).await {
+                Ok(user) => return Ok(Some(Self::with_store(user, store, password.clone(), config))),
+                Err(e) => failures.push(format!("{}: does not import: {}", snapshot.display(), e)),
+            }
+        }
+
+        let reason = if unreadable == snapshots.len() {
+            "none of them could be read"
+        } else {
+            "the password is wrong, or the snapshots that could be read are corrupt"
+        };
+        anyhow::bail!(
+            "None of the {} snapshots of {} imports, {}. They were left in place:\n{}",
+            snapshots.len(),
+            name,
+            reason,
+            failures.join("\n")
+        )
+    }
+
+    /// Restore the user from its newest snapshot, or create it if there is none. Fails like
+    /// [`PersistentUser::restore`] if there are snapshots but none imports
+    pub async fn restore_or<F: FnOnce(U::Transport) -> U>(
+        dir: &Path,
+        name: &str,
+        password: &Password,
+        transport: U::Transport,
+        config: StoreConfig,
+        create: F,
+    ) -> Result<Self> {
+        match Self::restore(dir, name, password, transport.clone(), config).await? {
+            Some(user) => Ok(user),
+            None => Self::new(create(transport), dir, name, password, config),
+        }
+    }
+
+    /// Snapshot the state now, regardless of the operation count
+    pub async fn snapshot(&mut self) -> Result<PathBuf> {
+        let state = self.user.export(self.password.expose()).await?;
+        let path = self.store.save(&state)?;
+        self.pending = 0;
+        self.snapshot_error = None;
+        Ok(path)
+    }
+
+    // Count an operation, snapshotting once enough of them were made
+    async fn touch(&mut self) {
+        self.pending += 1;
+        if self.pending >= self.every {
+            if let Err(e) = self.snapshot().await {
+                self.snapshot_error = Some(e);
+            }
+        }
+    }
+
+    // Count the operation if it succeeded, passing its result through
+    async fn counted<R>(&mut self, result: Result<R>) -> Result<R> {
+        if result.is_ok() {
+            self.touch().await;
+        }
+        result
+    }
+}
+
+impl<U> Deref for PersistentUser<U> {
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        &self.user
+    }
+}
+
+impl<T: Transport> PersistentUser<Author<T>> {
+    pub async fn send_announce(&mut self) -> Result<Address> {
+        let result = self.user.send_announce().await;
+        self.counted(result).await
+    }
+
+    pub async fn send_keyload(&mut self, link_to: &Address, keys: &Vec<Identifier>) -> Result<(Address, Option<Address>)> {
+        let result = self.user.send_keyload(link_to, keys).await;
+        self.counted(result).await
+    }
+
+    pub async fn send_keyload_for_everyone(&mut self, link_to: &Address) -> Result<(Address, Option<Address>)> {
+        let result = self.user.send_keyload_for_everyone(link_to).await;
+        self.counted(result).await
+    }
+
+    pub async fn receive_subscribe(&mut self, link: &Address) -> Result<()> {
+        let result = self.user.receive_subscribe(link).await;
+        self.counted(result).await
+    }
+
+    pub async fn receive_msg(&mut self, link: &Address) -> Result<UnwrappedMessage> {
+        let result = self.user.receive_msg(link).await;
+        self.counted(result).await
+    }
+
+    pub async fn sync_state(&mut self) {
+        self.user.sync_state().await;
+        self.touch().await
+    }
+
+    pub async fn store_psk(&mut self, pskid: PskId, psk: Psk) -> Result<()> {
+        let result = self.user.store_psk(pskid, psk);
+        self.counted(result).await
+    }
+
+    pub async fn remove_psk(&mut self, pskid: PskId) -> Result<()> {
+        let result = self.user.remove_psk(pskid);
+        self.counted(result).await
+    }
+}
+
+impl<T: Transport> PersistentUser<Subscriber<T>> {
+    pub async fn receive_announcement(&mut self, link: &Address) -> Result<()> {
+        let result = self.user.receive_announcement(link).await;
+        self.counted(result).await
+    }
+
+    pub async fn send_subscribe(&mut self, link: &Address) -> Result<Address> {
+        let result = self.user.send_subscribe(link).await;
+        self.counted(result).await
+    }
+
+    pub async fn receive_msg(&mut self, link: &Address) -> Result<UnwrappedMessage> {
+        let result = self.user.receive_msg(link).await;
+        self.counted(result).await
+    }
+
+    pub async fn sync_state(&mut self) {
+        self.user.sync_state().await;
+        self.touch().await
+    }
+
+    pub async fn store_psk(&mut self, pskid: PskId, psk: Psk) -> Result<()> {
+        let result = self.user.store_psk(pskid, psk);
+        self.counted(result).await
+    }
+
+    pub async fn remove_psk(&mut self, pskid: PskId) -> Result<()> {
+        let result = self.user.remove_psk(pskid);
+        self.counted(result).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<U: Publisher + Persist> Publisher for PersistentUser<U> {
+    async fn send_signed_packet(
+        &mut self,
+        link_to: &Address,
+        public_payload: &Bytes,
+        masked_payload: &Bytes,
+    ) -> Result<(Address, Option<Address>)> {
+        let result = self.user.send_signed_packet(link_to, public_payload, masked_payload).await;
+        self.counted(result).await
+    }
+
+    async fn send_tagged_packet(
+        &mut self,
+        link_to: &Address,
+        public_payload: &Bytes,
+        masked_payload: &Bytes,
+    ) -> Result<(Address, Option<Address>)> {
+        let result = self.user.send_tagged_packet(link_to, public_payload, masked_payload).await;
+        self.counted(result).await
+    }
+}
+
+impl<U: Reader + Persist> PersistentUser<U> {
+    /// Fetch every new message, counting the fetch as a single receive if anything was retrieved
+    pub async fn fetch_all_next_msgs(&mut self) -> Vec<UnwrappedMessage> {
+        let mut retrieved = Vec::new();
+        loop {
+            let msgs = self.user.fetch_next_msgs().await;
+            if msgs.is_empty() {
+                break;
+            }
+            retrieved.extend(msgs);
+        }
+        if !retrieved.is_empty() {
+            self.touch().await;
+        }
+        retrieved
+    }
+}
+
+#[async_trait(?Send)]
+impl<U: Reader + Persist> Reader for PersistentUser<U> {
+    async fn fetch_next_msgs(&mut self) -> Vec<UnwrappedMessage> {
+        let msgs = self.user.fetch_next_msgs().await;
+        if !msgs.is_empty() {
+            self.touch().await;
+        }
+        msgs
+    }
+}
@@ -0,0 +1,73 @@
+use crate::reader::Reader;
+use futures::stream::{self, AbortHandle, Abortable, LocalBoxStream, StreamExt};
+use iota_streams::app_channels::api::tangle::UnwrappedMessage;
+use std::{collections::VecDeque, time::Duration};
+
+/// How often a followed channel is polled. The reader polls again right away while messages keep
+/// coming, and waits `interval` after a poll that found nothing, growing the wait by `backoff` with
+/// every further empty poll up to `max_interval`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FollowConfig {
+    pub interval: Duration,
+    pub max_interval: Duration,
+    pub backoff: f64,
+}
+
+impl Default for FollowConfig {
+    fn default() -> Self {
+        FollowConfig {
+            interval: Duration::from_millis(500),
+            max_interval: Duration::from_secs(30),
+            backoff: 2.0,
+        }
+    }
+}
+
+impl FollowConfig {
+    fn next_wait(&self, wait: Option<Duration>) -> Duration {
+        match wait {
+            Some(wait) => wait.mul_f64(self.backoff.max(1.0)).min(self.max_interval),
+            None => self.interval,
+        }
+    }
+}
+
+/// Messages of a followed channel, ending once aborted
+pub type MessageStream<'a> = Abortable<LocalBoxStream<'a, UnwrappedMessage>>;
+
+struct Follow<'a, R> {
+    reader: &'a mut R,
+    pending: VecDeque<UnwrappedMessage>,
+    // Time to wait before the next poll, none while messages keep coming
+    wait: Option<Duration>,
+}
+
+/// Follow the channel as the reader, yielding messages as they are published. The stream never ends on
+/// its own: it ends when the returned handle is aborted, and stops polling when dropped
+pub fn follow<R: Reader>(reader: &mut R, config: FollowConfig) -> (MessageStream<'_>, AbortHandle) {
+    let state = Follow {
+        reader,
+        pending: VecDeque::new(),
+        wait: None,
+    };
+    let messages = stream::unfold(state, move |mut state| async move {
+        loop {
+            if let Some(msg) = state.pending.pop_front() {
+                return Some((msg, state));
+            }
+            if let Some(wait) = state.wait {
+                tokio::time::sleep(wait).await;
+            }
+            let msgs = state.reader.fetch_next_msgs().await;
+            state.wait = if msgs.is_empty() {
+                Some(config.next_wait(state.wait))
+            } else {
+                None
+            };
+            state.pending.extend(msgs);
+        }
+    });
+
+    let (handle, registration) = AbortHandle::new_pair();
+    (Abortable::new(messages.boxed_local(), registration), handle)
+}
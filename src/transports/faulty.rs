@@ -0,0 +1,239 @@
+use async_trait::async_trait;
+use iota_streams::{
+    app::transport::{self, TransportDetails, TransportOptions},
+    app_channels::api::tangle::{Address, Message, Transport},
+    core::Result,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+/// Faults that can be applied to a sent message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// The message never arrives
+    Drop,
+    /// The message is held back for the provided number of transport operations (sends and fetches)
+    Delay(usize),
+    /// The message arrives twice
+    Duplicate,
+    /// The message is held back until the next message has been sent
+    Reorder,
+}
+
+/// Probabilities of each fault being applied to a sent message. Scripted faults (see
+/// [`FaultyTransport::inject_next`]) take precedence over these.
+#[derive(Clone, Debug, Default)]
+pub struct FaultConfig {
+    pub drop: f64,
+    pub delay: f64,
+    pub duplicate: f64,
+    pub reorder: f64,
+    /// Number of transport operations a randomly delayed message is held back for
+    pub delay_ops: usize,
+    /// Seed of the random generator deciding which faults are applied
+    pub seed: u64,
+}
+
+impl FaultConfig {
+    fn pick(&self, rng: &mut StdRng) -> Option<Fault> {
+        if rng.gen_bool(self.drop) {
+            Some(Fault::Drop)
+        } else if rng.gen_bool(self.delay) {
+            Some(Fault::Delay(self.delay_ops))
+        } else if rng.gen_bool(self.duplicate) {
+            Some(Fault::Duplicate)
+        } else if rng.gen_bool(self.reorder) {
+            Some(Fault::Reorder)
+        } else {
+            None
+        }
+    }
+}
+
+/// A fault that was applied to a sent message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppliedFault {
+    pub link: String,
+    pub fault: Fault,
+}
+
+struct FaultState {
+    config: FaultConfig,
+    rng: StdRng,
+    script: VecDeque<Option<Fault>>,
+    delayed: Vec<(usize, Message)>,
+    reordered: Option<Message>,
+    applied: Vec<AppliedFault>,
+}
+
+/// Transport wrapper that drops, delays, duplicates and reorders messages on their way to the inner
+/// transport. Faults are picked per sent message, either from a script or by probability. The fault
+/// state is shared between all clones of the transport.
+#[derive(Clone)]
+pub struct FaultyTransport<T> {
+    inner: T,
+    state: Rc<RefCell<FaultState>>,
+}
+
+impl<T: Transport> FaultyTransport<T> {
+    /// Fails if a fault probability is not between 0 and 1
+    pub fn new(inner: T, config: FaultConfig) -> Result<Self> {
+        for p in &[config.drop, config.delay, config.duplicate, config.reorder] {
+            anyhow::ensure!(
+                (0.0..=1.0).contains(p),
+                "Fault probabilities must be between 0 and 1, got {}",
+                p
+            );
+        }
+        Ok(Self::with_config(inner, config))
+    }
+
+    /// A transport that only applies scripted faults
+    pub fn scripted(inner: T) -> Self {
+        Self::with_config(inner, FaultConfig::default())
+    }
+
+    fn with_config(inner: T, config: FaultConfig) -> Self {
+        let state = FaultState {
+            rng: StdRng::seed_from_u64(config.seed),
+            config,
+            script: VecDeque::new(),
+            delayed: Vec::new(),
+            reordered: None,
+            applied: Vec::new(),
+        };
+        FaultyTransport {
+            inner,
+            state: Rc::new(RefCell::new(state)),
+        }
+    }
+
+    /// Script the fault applied to the next sent message. Scripted faults are consumed in order, one
+    /// per sent message, and `None` lets a message through untouched.
+    pub fn inject_next(&self, fault: Option<Fault>) {
+        self.state.borrow_mut().script.push_back(fault);
+    }
+
+    /// Every fault applied so far
+    pub fn applied(&self) -> Vec<AppliedFault> {
+        self.state.borrow().applied.clone()
+    }
+
+    /// Deliver every delayed or reordered message that is still being held back
+    pub async fn flush(&mut self) -> Result<()> {
+        let held = {
+            let mut state = self.state.borrow_mut();
+            let mut held: Vec<Message> = state.delayed.drain(..).map(|(_, msg)| msg).collect();
+            held.extend(state.reordered.take());
+            held
+        };
+        for msg in &held {
+            self.inner.send_message(msg).await?;
+        }
+        Ok(())
+    }
+
+    // Count down delayed messages, delivering the ones that are due
+    async fn tick(&mut self) -> Result<()> {
+        let due = {
+            let mut state = self.state.borrow_mut();
+            let mut due = Vec::new();
+            let mut pending = Vec::new();
+            for (ops, msg) in state.delayed.drain(..) {
+                if ops <= 1 {
+                    due.push(msg);
+                } else {
+                    pending.push((ops - 1, msg));
+                }
+            }
+            state.delayed = pending;
+            due
+        };
+        for msg in &due {
+            self.inner.send_message(msg).await?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Transport> TransportOptions for FaultyTransport<T> {
+    type SendOptions = T::SendOptions;
+    fn get_send_options(&self) -> Self::SendOptions {
+        self.inner.get_send_options()
+    }
+    fn set_send_options(&mut self, opt: Self::SendOptions) {
+        self.inner.set_send_options(opt)
+    }
+
+    type RecvOptions = T::RecvOptions;
+    fn get_recv_options(&self) -> Self::RecvOptions {
+        self.inner.get_recv_options()
+    }
+    fn set_recv_options(&mut self, opt: Self::RecvOptions) {
+        self.inner.set_recv_options(opt)
+    }
+}
+
+#[async_trait(?Send)]
+impl<T: Transport> TransportDetails<Address> for FaultyTransport<T> {
+    type Details = T::Details;
+    async fn get_link_details(&mut self, link: &Address) -> Result<Self::Details> {
+        self.inner.get_link_details(link).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<T: Transport> transport::Transport<Address, Message> for FaultyTransport<T> {
+    async fn send_message(&mut self, msg: &Message) -> Result<()> {
+        self.tick().await?;
+
+        let (fault, reordered) = {
+            let mut state = self.state.borrow_mut();
+            let fault = match state.script.pop_front() {
+                Some(scripted) => scripted,
+                None => {
+                    let FaultState { config, rng, .. } = &mut *state;
+                    config.pick(rng)
+                }
+            };
+            if let Some(fault) = fault {
+                state.applied.push(AppliedFault {
+                    link: msg.binary.link.to_string(),
+                    fault,
+                });
+            }
+            // A message held back for reordering is released once this one has been delivered
+            let reordered = match fault {
+                Some(Fault::Reorder) => None,
+                _ => state.reordered.take(),
+            };
+            (fault, reordered)
+        };
+
+        match fault {
+            None => self.inner.send_message(msg).await?,
+            Some(Fault::Drop) => {}
+            Some(Fault::Delay(ops)) => self.state.borrow_mut().delayed.push((ops, msg.clone())),
+            Some(Fault::Duplicate) => {
+                self.inner.send_message(msg).await?;
+                self.inner.send_message(msg).await?;
+            }
+            Some(Fault::Reorder) => {
+                let previous = self.state.borrow_mut().reordered.replace(msg.clone());
+                if let Some(previous) = previous {
+                    self.inner.send_message(&previous).await?;
+                }
+            }
+        }
+
+        if let Some(reordered) = reordered {
+            self.inner.send_message(&reordered).await?;
+        }
+        Ok(())
+    }
+
+    async fn recv_messages(&mut self, link: &Address) -> Result<Vec<Message>> {
+        self.tick().await?;
+        self.inner.recv_messages(link).await
+    }
+}
@@ -0,0 +1,25 @@
+use iota_streams::{
+    app::transport::tangle::client::Client,
+    app_channels::api::tangle::BucketTransport,
+};
+use std::{cell::RefCell, rc::Rc};
+
+pub mod faulty;
+pub mod recording;
+pub use faulty::{Fault, FaultConfig, FaultyTransport};
+pub use recording::{Fixture, RecordingTransport, ReplayTransport};
+
+/// In-memory transport. The bucket is shared between all the users of an example, hence the
+/// `Rc<RefCell<BucketTransport>>`
+pub type OfflineTransport = Rc<RefCell<BucketTransport>>;
+
+/// Create a fresh in-memory transport. Nothing sent through it leaves the process, so examples run
+/// against it without any network access
+pub fn offline() -> OfflineTransport {
+    Rc::new(RefCell::new(BucketTransport::new()))
+}
+
+/// Create a tangle client transport that sends to and fetches from the provided node
+pub fn node(node_url: &str) -> Client {
+    Client::new_from_url(node_url)
+}
@@ -0,0 +1,212 @@
+use async_trait::async_trait;
+use iota_streams::{
+    app::{
+        message::BinaryMessage,
+        transport::{self, TransportDetails, TransportOptions},
+    },
+    app_channels::api::tangle::{Address, Message, Transport},
+    core::Result,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    path::Path,
+    rc::Rc,
+    str::FromStr,
+};
+
+/// A single message as stored in a fixture file. Links are stored in their string form and the
+/// binary body is hex encoded
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedMessage {
+    pub link: String,
+    pub prev_link: String,
+    pub timestamp: u64,
+    pub body: String,
+}
+
+impl RecordedMessage {
+    pub fn from_message(msg: &Message) -> Self {
+        RecordedMessage {
+            link: msg.binary.link.to_string(),
+            prev_link: msg.binary.prev_link.to_string(),
+            timestamp: msg.timestamp,
+            body: hex::encode(&msg.binary.body.bytes),
+        }
+    }
+
+    pub fn to_message(&self) -> Result<Message> {
+        let link = Address::from_str(&self.link)?;
+        let prev_link = Address::from_str(&self.prev_link)?;
+        let body = hex::decode(&self.body)?;
+        Ok(Message::with_timestamp(
+            BinaryMessage::new(link, prev_link, body.into()),
+            self.timestamp,
+        ))
+    }
+}
+
+/// Everything a recorded run sent and fetched
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fixture {
+    /// Every message that passed through the transport, in the order it was first seen
+    pub messages: Vec<RecordedMessage>,
+    /// Links of the messages sent through the transport, in the order they were sent
+    pub sent: Vec<String>,
+}
+
+impl Fixture {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = std::fs::read(path)?;
+        Ok(serde_json::from_slice(&contents)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn record(&mut self, msg: &Message) {
+        let recorded = RecordedMessage::from_message(msg);
+        if !self.messages.contains(&recorded) {
+            self.messages.push(recorded);
+        }
+    }
+}
+
+/// Transport wrapper that records every message sent and fetched through the inner transport. The
+/// recording is shared between all clones of the transport, so it covers every user of a run.
+#[derive(Clone)]
+pub struct RecordingTransport<T> {
+    inner: T,
+    fixture: Rc<RefCell<Fixture>>,
+}
+
+impl<T: Transport> RecordingTransport<T> {
+    pub fn new(inner: T) -> Self {
+        RecordingTransport {
+            inner,
+            fixture: Rc::new(RefCell::new(Fixture::default())),
+        }
+    }
+
+    /// A copy of everything recorded so far
+    pub fn fixture(&self) -> Fixture {
+        self.fixture.borrow().clone()
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.fixture.borrow().save(path)
+    }
+}
+
+impl<T: Transport> TransportOptions for RecordingTransport<T> {
+    type SendOptions = T::SendOptions;
+    fn get_send_options(&self) -> Self::SendOptions {
+        self.inner.get_send_options()
+    }
+    fn set_send_options(&mut self, opt: Self::SendOptions) {
+        self.inner.set_send_options(opt)
+    }
+
+    type RecvOptions = T::RecvOptions;
+    fn get_recv_options(&self) -> Self::RecvOptions {
+        self.inner.get_recv_options()
+    }
+    fn set_recv_options(&mut self, opt: Self::RecvOptions) {
+        self.inner.set_recv_options(opt)
+    }
+}
+
+#[async_trait(?Send)]
+impl<T: Transport> TransportDetails<Address> for RecordingTransport<T> {
+    type Details = T::Details;
+    async fn get_link_details(&mut self, link: &Address) -> Result<Self::Details> {
+        self.inner.get_link_details(link).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<T: Transport> transport::Transport<Address, Message> for RecordingTransport<T> {
+    async fn send_message(&mut self, msg: &Message) -> Result<()> {
+        self.inner.send_message(msg).await?;
+        let mut fixture = self.fixture.borrow_mut();
+        fixture.record(msg);
+        fixture.sent.push(msg.binary.link.to_string());
+        Ok(())
+    }
+
+    async fn recv_messages(&mut self, link: &Address) -> Result<Vec<Message>> {
+        let msgs = self.inner.recv_messages(link).await?;
+        let mut fixture = self.fixture.borrow_mut();
+        for msg in &msgs {
+            fixture.record(msg);
+        }
+        Ok(msgs)
+    }
+}
+
+/// Transport that serves the messages of a recorded run back without touching the network.
+///
+/// Messages are served by link, so a replayed run needs to generate the same links as the recorded
+/// one, i.e. the users need to be generated from the same seeds. Sending a message that was not sent
+/// in the recording is reported as an error, as the replayed run has diverged from the recorded one.
+#[derive(Clone)]
+pub struct ReplayTransport {
+    messages: Rc<HashMap<String, Vec<RecordedMessage>>>,
+    sent: Rc<HashSet<String>>,
+}
+
+impl ReplayTransport {
+    pub fn new(fixture: Fixture) -> Self {
+        let mut messages: HashMap<String, Vec<RecordedMessage>> = HashMap::new();
+        for msg in fixture.messages {
+            messages.entry(msg.link.clone()).or_default().push(msg);
+        }
+        ReplayTransport {
+            messages: Rc::new(messages),
+            sent: Rc::new(fixture.sent.into_iter().collect()),
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(Self::new(Fixture::load(path)?))
+    }
+}
+
+impl TransportOptions for ReplayTransport {
+    type SendOptions = ();
+    fn get_send_options(&self) -> Self::SendOptions {}
+    fn set_send_options(&mut self, _opt: Self::SendOptions) {}
+
+    type RecvOptions = ();
+    fn get_recv_options(&self) -> Self::RecvOptions {}
+    fn set_recv_options(&mut self, _opt: Self::RecvOptions) {}
+}
+
+#[async_trait(?Send)]
+impl TransportDetails<Address> for ReplayTransport {
+    type Details = ();
+    async fn get_link_details(&mut self, _link: &Address) -> Result<Self::Details> {
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl transport::Transport<Address, Message> for ReplayTransport {
+    async fn send_message(&mut self, msg: &Message) -> Result<()> {
+        let link = msg.binary.link.to_string();
+        if !self.sent.contains(&link) {
+            anyhow::bail!("Replay diverged from the recording, message {} was never sent", link);
+        }
+        Ok(())
+    }
+
+    async fn recv_messages(&mut self, link: &Address) -> Result<Vec<Message>> {
+        match self.messages.get(&link.to_string()) {
+            Some(msgs) => msgs.iter().map(RecordedMessage::to_message).collect(),
+            None => anyhow::bail!("Message {} not found in recording", link),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use iota_streams::app_channels::api::tangle::ChannelType;
+use std::time::{Duration, Instant};
+use streams_examples::{
+    bench::{self, BenchConfig, Samples},
+    examples::SeedProvider,
+    transports,
+};
+
+#[test]
+fn percentiles_use_nearest_rank() {
+    let mut samples = Samples::new();
+    assert_eq!(samples.percentile(50.0), Duration::default());
+
+    for _ in 0..4 {
+        samples.record(Instant::now(), 2);
+    }
+    let stats = samples.stats("op");
+    assert_eq!(stats.calls, 4);
+    assert_eq!(stats.items, 8);
+    assert!(samples.percentile(50.0) <= samples.percentile(100.0));
+    assert!(stats.p99_ms <= stats.max_ms);
+}
+
+#[tokio::test]
+async fn benchmarks_every_op() -> Result<()> {
+    let config = BenchConfig {
+        channel_type: ChannelType::MultiBranch,
+        messages: 4,
+        subscribers: 2,
+        keyloads: 2,
+        rounds: 2,
+    };
+    let result = bench::run(config, transports::offline, &mut SeedProvider::deterministic(3)).await?;
+
+    let calls: Vec<(&str, usize)> = result.ops.iter().map(|op| (op.op.as_str(), op.calls)).collect();
+    assert_eq!(
+        calls,
+        vec![("send_keyload", 4), ("send_signed_packet", 8), ("fetch_all_next_msgs", 4)]
+    );
+    Ok(())
+}
@@ -0,0 +1,195 @@
+mod common;
+
+use anyhow::Result;
+use common::Channel;
+use iota_streams::app_channels::api::tangle::{Bytes, ChannelType};
+use std::convert::TryFrom;
+use streams_examples::{
+    chunking::{self, content_hash, Assembler, Chunk, ChunkError, Manifest, TransferKey, MAX_CHUNKS},
+    payload::{Codec, Format},
+    runner::RunDir,
+    transports::{self, Fault, FaultyTransport},
+};
+
+fn content(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+#[tokio::test]
+async fn reassembles_transfers() -> Result<()> {
+    let Channel {
+        mut author,
+        mut subscriber,
+        announcement_link,
+        ..
+    } = Channel::new(transports::offline(), 16, ChannelType::SingleBranch).await?;
+
+    let data = content(100_000);
+    let transfer = chunking::send_chunked(&mut author, &announcement_link, "data.bin", &data, 8 * 1024).await?;
+    assert_eq!(transfer.chunk_links.len() as u32, transfer.manifest.chunks);
+
+    let dir = RunDir::new("chunking")?;
+    let path = dir.path().join("small.txt");
+    std::fs::write(&path, b"fits in a single chunk")?;
+    let file = chunking::send_file(&mut author, transfer.last_link(), &path, chunking::DEFAULT_CHUNK_SIZE).await?;
+    assert_eq!(file.manifest.name, "small.txt");
+
+    let mut assembler = Assembler::new();
+    assembler.push(&subscriber.fetch_all_next_msgs().await);
+    assert_eq!(assembler.transfers().len(), 2);
+    let publisher = author.get_public_key();
+    assert_eq!(assembler.finish(&TransferKey::new(publisher, &transfer.manifest.id))?, data);
+    assert_eq!(
+        assembler.finish(&TransferKey::new(publisher, &file.manifest.id))?,
+        b"fits in a single chunk"
+    );
+
+    // Chunk sizes that do not fit a manifest are refused rather than truncated
+    if let Ok(chunk_size) = usize::try_from(u64::from(u32::MAX) + 1) {
+        assert!(chunking::send_chunked(&mut author, file.last_link(), "data.bin", &data, chunk_size).await.is_err());
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn reports_missing_chunks() -> Result<()> {
+    let transport = FaultyTransport::scripted(transports::offline());
+    // Announcement, manifest and the first two chunks arrive, the third chunk is dropped
+    for _ in 0..4 {
+        transport.inject_next(None);
+    }
+    transport.inject_next(Some(Fault::Drop));
+    let Channel {
+        mut author,
+        mut subscriber,
+        announcement_link,
+        ..
+    } = Channel::new(transport, 16, ChannelType::SingleBranch).await?;
+
+    let transfer = chunking::send_chunked(&mut author, &announcement_link, "data.bin", &content(5_000), 1_000).await?;
+    let mut assembler = Assembler::new();
+    assembler.push(&subscriber.fetch_all_next_msgs().await);
+
+    let key = TransferKey::new(author.get_public_key(), &transfer.manifest.id);
+    let missing = assembler.missing(&key)?;
+    assert_eq!(missing.first(), Some(&2));
+    assert_eq!(assembler.finish(&key), Err(ChunkError::MissingChunks(missing)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn checks_content_hash() -> Result<()> {
+    let Channel {
+        mut author,
+        mut subscriber,
+        announcement_link,
+        ..
+    } = Channel::new(transports::offline(), 16, ChannelType::SingleBranch).await?;
+
+    let manifest = Manifest {
+        id: content_hash(b"original"),
+        name: "tampered".to_string(),
+        size: 8,
+        chunk_size: 8,
+        chunks: 1,
+    };
+    let chunk = Chunk {
+        transfer: manifest.id.clone(),
+        index: 0,
+        data: b"tampered".to_vec(),
+    };
+    let codec = Codec::new(Format::Json);
+    let (manifest_link, _seq_link) = author
+        .send_signed_packet(&announcement_link, &Bytes::default(), &codec.encode(&manifest)?)
+        .await?;
+    author
+        .send_signed_packet(&manifest_link, &Bytes::default(), &codec.encode(&chunk)?)
+        .await?;
+
+    let mut assembler = Assembler::new();
+    assembler.push(&subscriber.fetch_all_next_msgs().await);
+    let key = TransferKey::new(author.get_public_key(), &manifest.id);
+    assert!(matches!(assembler.finish(&key), Err(ChunkError::HashMismatch { .. })));
+    // The bad chunk is dropped, so the transfer waits for it again
+    assert_eq!(assembler.missing(&key)?, vec![0]);
+    let unknown = TransferKey::new(author.get_public_key(), "unknown");
+    assert_eq!(assembler.finish(&unknown), Err(ChunkError::MissingManifest));
+    Ok(())
+}
+
+#[tokio::test]
+async fn ignores_chunks_injected_by_other_publishers() -> Result<()> {
+    let Channel {
+        mut author,
+        mut subscriber,
+        announcement_link,
+        ..
+    } = Channel::new(transports::offline(), 16, ChannelType::SingleBranch).await?;
+    let Channel {
+        author: mut intruder,
+        subscriber: mut intruder_reader,
+        announcement_link: intruder_link,
+        ..
+    } = Channel::new(transports::offline(), 116, ChannelType::SingleBranch).await?;
+
+    let data = content(2_500);
+    let transfer = chunking::send_chunked(&mut author, &announcement_link, "data.bin", &data, 1_000).await?;
+
+    // Another publisher reuses the public id of the transfer, and sends chunks of it that are bogus,
+    // out of range or too long
+    let codec = Codec::new(Format::Bincode);
+    let (mut prev_msg_link, _seq_link) = intruder
+        .send_signed_packet(&intruder_link, &Bytes::default(), &Codec::new(Format::Json).encode(&transfer.manifest)?)
+        .await?;
+    for (index, len) in &[(0, 1_000), (1, 1_000), (2, 1_000), (3, 1_000), (MAX_CHUNKS, 1)] {
+        let chunk = Chunk {
+            transfer: transfer.manifest.id.clone(),
+            index: *index,
+            data: vec![0; *len],
+        };
+        let (msg_link, _seq_link) = intruder
+            .send_signed_packet(&prev_msg_link, &Bytes::default(), &codec.encode(&chunk)?)
+            .await?;
+        prev_msg_link = msg_link;
+    }
+
+    // Retrieved ahead of the genuine transfer
+    let retrieved = [
+        intruder_reader.fetch_all_next_msgs().await,
+        subscriber.fetch_all_next_msgs().await,
+    ]
+    .concat();
+    let mut assembler = Assembler::new();
+    assembler.push(&retrieved);
+    assert_eq!(assembler.finish(&TransferKey::new(author.get_public_key(), &transfer.manifest.id))?, data);
+
+    // Only its chunks that fit the manifest are kept, the last one is too long
+    let intruded = TransferKey::new(intruder.get_public_key(), &transfer.manifest.id);
+    assert_eq!(assembler.missing(&intruded)?, vec![2]);
+    Ok(())
+}
+
+#[test]
+fn rejects_inconsistent_manifests() {
+    let manifest = Manifest {
+        id: content_hash(b""),
+        name: "huge".to_string(),
+        size: 1_000,
+        chunk_size: 100,
+        chunks: u32::MAX,
+    };
+    assert!(matches!(manifest.validate(), Err(ChunkError::InvalidManifest(_))));
+
+    let oversized = Manifest {
+        size: u64::from(MAX_CHUNKS) + 1,
+        chunk_size: 1,
+        chunks: MAX_CHUNKS + 1,
+        ..manifest.clone()
+    };
+    assert!(matches!(oversized.validate(), Err(ChunkError::InvalidManifest(_))));
+    let empty_chunks = Manifest {
+        chunk_size: 0,
+        ..manifest.clone()
+    };
+    assert!(empty_chunks.validate().is_err());
+}
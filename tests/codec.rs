@@ -0,0 +1,107 @@
+mod common;
+
+use anyhow::Result;
+use common::{send_and_fetch, Packet};
+use iota_streams::app_channels::api::tangle::{Bytes, MessageContent};
+use serde::{Deserialize, Serialize};
+use streams_examples::payload::{decode_masked, decode_public, tag, Codec, CodecError, Format, Record};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Reading {
+    sensor: String,
+    celsius: f64,
+    samples: Vec<u32>,
+}
+
+impl Record for Reading {
+    const SCHEMA: &'static str = "reading";
+    const VERSION: u16 = 2;
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Location {
+    site: String,
+}
+
+impl Record for Location {
+    const SCHEMA: &'static str = "location";
+    const VERSION: u16 = 1;
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct ReadingV1 {
+    sensor: String,
+}
+
+impl Record for ReadingV1 {
+    const SCHEMA: &'static str = "reading";
+    const VERSION: u16 = 1;
+}
+
+#[tokio::test]
+async fn round_trips_every_format() -> Result<()> {
+    let reading = Reading {
+        sensor: "greenhouse".to_string(),
+        celsius: 21.5,
+        samples: vec![3, 1, 4],
+    };
+    let location = Location {
+        site: "north".to_string(),
+    };
+
+    let mut payloads = Vec::new();
+    for format in Format::ALL.iter() {
+        let codec = Codec::new(*format);
+        payloads.push(Packet::Signed(codec.encode(&location)?, codec.encode(&reading)?));
+    }
+    let retrieved = send_and_fetch(15, &payloads).await?;
+    assert_eq!(retrieved.len(), Format::ALL.len());
+
+    for (msg, format) in retrieved.iter().zip(Format::ALL.iter()) {
+        assert_eq!(decode_masked::<Reading>(msg)?, reading);
+        assert_eq!(decode_public::<Location>(msg)?, location);
+        if let MessageContent::SignedPacket { masked_payload, .. } = &msg.body {
+            let tag = tag(masked_payload)?;
+            assert_eq!((tag.format, tag.schema.as_str(), tag.version), (*format, "reading", 2));
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn reports_typed_errors() -> Result<()> {
+    let codec = Codec::new(Format::Cbor);
+    let reading = Reading {
+        sensor: "greenhouse".to_string(),
+        celsius: 21.5,
+        samples: Vec::new(),
+    };
+    let retrieved = send_and_fetch(
+        15,
+        &[
+            Packet::Signed(Bytes::default(), codec.encode(&reading)?),
+            Packet::signed(b"", b"plain text"),
+        ],
+    )
+    .await?;
+
+    assert!(matches!(
+        decode_masked::<Location>(&retrieved[0]),
+        Err(CodecError::SchemaMismatch { expected: "location", .. })
+    ));
+    assert!(matches!(
+        decode_masked::<ReadingV1>(&retrieved[0]),
+        Err(CodecError::VersionMismatch {
+            expected: 1,
+            found: 2,
+            ..
+        })
+    ));
+    assert!(matches!(decode_public::<Reading>(&retrieved[0]), Err(CodecError::Empty)));
+    assert!(matches!(decode_masked::<Reading>(&retrieved[1]), Err(CodecError::Untyped)));
+
+    let mut truncated = codec.encode(&reading)?;
+    truncated.0.truncate(6);
+    assert!(matches!(codec.decode::<Reading>(&truncated), Err(CodecError::Truncated)));
+    Ok(())
+}
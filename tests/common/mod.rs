@@ -0,0 +1,92 @@
+// Shared by the integration tests, each of which uses only part of it
+#![allow(dead_code)]
+
+use anyhow::Result;
+use iota_streams::app_channels::api::tangle::{
+    Address, Author, Bytes, ChannelType, PublicKey, Subscriber, Transport, UnwrappedMessage,
+};
+use streams_examples::{examples::SeedProvider, transports};
+
+/// A packet to publish, with its public and masked payloads
+pub enum Packet {
+    Signed(Bytes, Bytes),
+    Tagged(Bytes, Bytes),
+}
+
+impl Packet {
+    pub fn signed(public: &[u8], masked: &[u8]) -> Self {
+        Packet::Signed(Bytes(public.to_vec()), Bytes(masked.to_vec()))
+    }
+
+    pub fn tagged(public: &[u8], masked: &[u8]) -> Self {
+        Packet::Tagged(Bytes(public.to_vec()), Bytes(masked.to_vec()))
+    }
+}
+
+/// An announced channel and a subscriber that received the announcement
+pub struct Channel<T> {
+    pub author: Author<T>,
+    pub subscriber: Subscriber<T>,
+    pub announcement_link: Address,
+    transport: T,
+}
+
+impl<T: Transport> Channel<T> {
+    /// The author seed is drawn from a provider seeded deterministically with `seed`
+    pub async fn new(transport: T, seed: u64, channel_type: ChannelType) -> Result<Self> {
+        let mut author = Author::new(
+            &SeedProvider::deterministic(seed).author_seed(),
+            channel_type,
+            transport.clone(),
+        );
+        let announcement_link = author.send_announce().await?;
+        let mut subscriber = Subscriber::new("SubscriberA", transport.clone());
+        subscriber.receive_announcement(&announcement_link).await?;
+        Ok(Channel {
+            author,
+            subscriber,
+            announcement_link,
+            transport,
+        })
+    }
+
+    /// Another subscriber that received the announcement, but did not subscribe
+    pub async fn reader(&self, name: &str) -> Result<Subscriber<T>> {
+        let mut reader = Subscriber::new(name, self.transport.clone());
+        reader.receive_announcement(&self.announcement_link).await?;
+        Ok(reader)
+    }
+
+    /// Subscribe the subscriber, and return its public key to send keyloads to
+    pub async fn subscribe(&mut self) -> Result<PublicKey> {
+        let subscribe_link = self.subscriber.send_subscribe(&self.announcement_link).await?;
+        self.author.receive_subscribe(&subscribe_link).await?;
+        Ok(PublicKey::from_bytes(self.subscriber.get_public_key().as_bytes())?)
+    }
+
+    /// Publish the packets as a chain attached to the announcement, and return the link of each
+    pub async fn send_chain(&mut self, packets: &[Packet]) -> Result<Vec<Address>> {
+        let mut links = Vec::new();
+        let mut prev_msg_link = self.announcement_link.clone();
+        for packet in packets {
+            let (msg_link, _seq_link) = match packet {
+                Packet::Signed(public, masked) => {
+                    self.author.send_signed_packet(&prev_msg_link, public, masked).await?
+                }
+                Packet::Tagged(public, masked) => {
+                    self.author.send_tagged_packet(&prev_msg_link, public, masked).await?
+                }
+            };
+            links.push(msg_link.clone());
+            prev_msg_link = msg_link;
+        }
+        Ok(links)
+    }
+}
+
+/// Publish the packets in a public single branch channel and fetch them as a subscriber
+pub async fn send_and_fetch(seed: u64, packets: &[Packet]) -> Result<Vec<UnwrappedMessage>> {
+    let mut channel = Channel::new(transports::offline(), seed, ChannelType::SingleBranch).await?;
+    channel.send_chain(packets).await?;
+    Ok(channel.subscriber.fetch_all_next_msgs().await)
+}
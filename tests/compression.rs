@@ -0,0 +1,116 @@
+mod common;
+
+use anyhow::Result;
+use common::{send_and_fetch, Channel, Packet};
+use iota_streams::app_channels::api::tangle::{Bytes, ChannelType, MessageContent, UnwrappedMessage};
+use serde::{Deserialize, Serialize};
+use streams_examples::{
+    examples::{
+        verify::{verify_with, Payload, VerifyOptions},
+        verify_messages,
+    },
+    payload::{
+        compress, compression::header, decode_masked, send_compressed, Codec, CodecError, CompressionError, Encoding,
+        Format, Record, MAX_DECOMPRESSED,
+    },
+    transports,
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Telemetry {
+    readings: Vec<u32>,
+}
+
+impl Record for Telemetry {
+    const SCHEMA: &'static str = "telemetry";
+    const VERSION: u16 = 1;
+}
+
+// Compress the payloads and send them in a public single branch channel
+async fn send_compressed_and_fetch(payloads: &[(Encoding, Bytes, Bytes)]) -> Result<Vec<UnwrappedMessage>> {
+    let mut packets = Vec::new();
+    for (encoding, public, masked) in payloads {
+        let (public, masked) = compress(*encoding, public, masked)?;
+        packets.push(Packet::Signed(public, masked));
+    }
+    send_and_fetch(17, &packets).await
+}
+
+#[tokio::test]
+async fn decompresses_transparently() -> Result<()> {
+    let telemetry = "temperature=21.5;".repeat(200);
+    let payloads: Vec<_> = Encoding::ALL
+        .iter()
+        .map(|encoding| (*encoding, Bytes(b"sensor-1".to_vec()), Bytes(telemetry.as_bytes().to_vec())))
+        .collect();
+    let retrieved = send_compressed_and_fetch(&payloads).await?;
+
+    for (msg, encoding) in retrieved.iter().zip(Encoding::ALL.iter()) {
+        if let MessageContent::SignedPacket {
+            public_payload,
+            masked_payload,
+            ..
+        } = &msg.body
+        {
+            let header = header(public_payload)?.expect("compression header");
+            assert_eq!(header.encoding, *encoding);
+            assert_eq!(header.size as usize, telemetry.len());
+            if *encoding != Encoding::Identity {
+                assert!(masked_payload.0.len() < telemetry.len());
+            }
+        }
+    }
+
+    let public = VerifyOptions {
+        payload: Payload::Public,
+        ..VerifyOptions::default()
+    };
+    assert!(verify_with(&["sensor-1"; 3], &retrieved, &public).is_ok());
+    let sent = vec![telemetry.as_str(); 3];
+    verify_messages(&sent, retrieved)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn decodes_compressed_records() -> Result<()> {
+    let telemetry = Telemetry {
+        readings: vec![7; 500],
+    };
+    let masked = Codec::new(Format::Cbor).encode(&telemetry)?;
+    let mut channel = Channel::new(transports::offline(), 17, ChannelType::SingleBranch).await?;
+    let announcement_link = channel.announcement_link.clone();
+    send_compressed(&mut channel.author, &announcement_link, Encoding::Zstd, &Bytes::default(), &masked).await?;
+    let retrieved = channel.subscriber.fetch_all_next_msgs().await;
+    assert_eq!(decode_masked::<Telemetry>(&retrieved[0])?, telemetry);
+    Ok(())
+}
+
+#[tokio::test]
+async fn reports_corrupt_payloads() -> Result<()> {
+    let (public, _masked) = compress(Encoding::Deflate, &Bytes::default(), &Bytes(b"original".to_vec()))?;
+    let retrieved = send_and_fetch(17, &[Packet::Signed(public, Bytes(b"not deflate".to_vec()))]).await?;
+
+    let error = verify_with(&[], &retrieved, &VerifyOptions::default()).unwrap_err();
+    assert_eq!(error.corrupt.len(), 1);
+    assert!(matches!(
+        decode_masked::<Telemetry>(&retrieved[0]),
+        Err(CodecError::Compression(_))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn rejects_oversized_headers() -> Result<()> {
+    // A header alone claims the payload decodes to 4 GiB, which is never allocated
+    let mut public = b"\0ce\x01".to_vec();
+    public.extend_from_slice(&u32::MAX.to_be_bytes());
+    let retrieved = send_and_fetch(17, &[Packet::Signed(Bytes(public), Bytes(b"tiny".to_vec()))]).await?;
+    assert!(matches!(
+        decode_masked::<Telemetry>(&retrieved[0]),
+        Err(CodecError::Compression(CompressionError::ExceedsLimit(u32::MAX)))
+    ));
+
+    let oversized = Bytes(vec![0; MAX_DECOMPRESSED as usize + 1]);
+    assert!(compress(Encoding::Zstd, &Bytes::default(), &oversized).is_err());
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! Runs every example against an in-memory transport. Examples verify the messages they retrieve
+//! and fail on any mismatch, so a regression in the Streams dependency fails the matching test.
+
+use anyhow::Result;
+use streams_examples::{examples, examples::SeedProvider, runner::RunDir, transports};
+
+// Set STREAMS_EXAMPLES_SEED to reproduce a failing run
+fn seeds() -> SeedProvider {
+    SeedProvider::from_env().unwrap()
+}
+
+#[tokio::test]
+async fn single_branch_public() -> Result<()> {
+    examples::single_branch_public::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn single_branch_private() -> Result<()> {
+    examples::single_branch_private::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn single_depth_public() -> Result<()> {
+    examples::single_depth_public::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn single_depth_private() -> Result<()> {
+    examples::single_depth_private::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn multi_branch_mixed_privacy() -> Result<()> {
+    examples::multi_branch_mixed_privacy::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn single_pub_per_branch() -> Result<()> {
+    examples::single_pub_per_branch::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn multi_pub_per_branch() -> Result<()> {
+    examples::multi_pub_per_branch::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn fetch_prev() -> Result<()> {
+    examples::fetch_prev::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn grant_and_revoke_access() -> Result<()> {
+    examples::grant_and_revoke_access::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn pk_keyloads() -> Result<()> {
+    examples::pk_keyloads::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn psk_keyloads() -> Result<()> {
+    examples::psk_keyloads::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn state_recovery() -> Result<()> {
+    let dir = RunDir::new("state_recovery")?;
+    examples::state_recovery::example(transports::offline(), &mut seeds(), dir.path()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn stateless_recovery() -> Result<()> {
+    examples::stateless_recovery::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn network_faults() -> Result<()> {
+    examples::network_faults::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn tagged_packets() -> Result<()> {
+    examples::tagged_packets::example(transports::offline(), &mut seeds()).await?;
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use iota_streams::app_channels::api::tangle::{Address, Author, Bytes, ChannelType, PublicKey, Subscriber, Transport};
+use streams_examples::{
+    examples::{group_by_publisher, verify_messages},
+    transports::{self, Fault, FaultConfig, FaultyTransport},
+};
+
+const SEED: &str = "FAULTSEED999999999999999999999999999999999999999999999999999999999999999999999999";
+const MSG_INPUTS: [&str; 5] = ["These", "Messages", "Are", "Sent", "Unreliably"];
+
+// Publish a public single branch chain, applying the fault to the 3rd message
+async fn publish<T: Transport>(transport: &FaultyTransport<T>, fault: Fault) -> Result<Address> {
+    let mut author = Author::new(SEED, ChannelType::SingleBranch, transport.clone());
+    let announcement_link = author.send_announce().await?;
+
+    let mut prev_msg_link = announcement_link.clone();
+    for (i, input) in MSG_INPUTS.iter().enumerate() {
+        if i == 2 {
+            transport.inject_next(Some(fault));
+        }
+        let (msg_link, _seq_link) = author
+            .send_signed_packet(&prev_msg_link, &Bytes::default(), &Bytes(input.as_bytes().to_vec()))
+            .await?;
+        prev_msg_link = msg_link;
+    }
+    Ok(announcement_link)
+}
+
+#[tokio::test]
+async fn dropped_message_stalls_retrieval() -> Result<()> {
+    let transport = FaultyTransport::scripted(transports::offline());
+    let announcement_link = publish(&transport, Fault::Drop).await?;
+    assert_eq!(transport.applied().len(), 1);
+    assert_eq!(transport.applied()[0].fault, Fault::Drop);
+
+    let mut subscriber = Subscriber::new("SubscriberA", transport.clone());
+    subscriber.receive_announcement(&announcement_link).await?;
+    let retrieved = subscriber.fetch_all_next_msgs().await;
+    assert_eq!(retrieved.len(), 2);
+    verify_messages(&MSG_INPUTS[..2], retrieved)
+}
+
+#[tokio::test]
+async fn delayed_message_is_retrieved_once_delivered() -> Result<()> {
+    let mut transport = FaultyTransport::scripted(transports::offline());
+    let announcement_link = publish(&transport, Fault::Delay(1000)).await?;
+
+    let mut subscriber = Subscriber::new("SubscriberA", transport.clone());
+    subscriber.receive_announcement(&announcement_link).await?;
+    let retrieved = subscriber.fetch_all_next_msgs().await;
+    assert_eq!(retrieved.len(), 2);
+
+    transport.flush().await?;
+    let retrieved = subscriber.fetch_all_next_msgs().await;
+    assert_eq!(retrieved.len(), 3);
+    verify_messages(&MSG_INPUTS[2..], retrieved)
+}
+
+#[tokio::test]
+async fn reordered_messages_are_retrieved_in_sequence() -> Result<()> {
+    let transport = FaultyTransport::scripted(transports::offline());
+    let announcement_link = publish(&transport, Fault::Reorder).await?;
+
+    let mut subscriber = Subscriber::new("SubscriberA", transport.clone());
+    subscriber.receive_announcement(&announcement_link).await?;
+    let retrieved = subscriber.fetch_all_next_msgs().await;
+    assert_eq!(retrieved.len(), MSG_INPUTS.len());
+    verify_messages(&MSG_INPUTS, retrieved)
+}
+
+#[tokio::test]
+async fn duplicated_message_stalls_retrieval() -> Result<()> {
+    let transport = FaultyTransport::scripted(transports::offline());
+    let announcement_link = publish(&transport, Fault::Duplicate).await?;
+    assert_eq!(transport.applied()[0].fault, Fault::Duplicate);
+
+    // Readers refuse a link holding more than one message, so the duplicate is as good as lost
+    let mut subscriber = Subscriber::new("SubscriberA", transport.clone());
+    subscriber.receive_announcement(&announcement_link).await?;
+    let retrieved = subscriber.fetch_all_next_msgs().await;
+    assert_eq!(retrieved.len(), 2);
+    verify_messages(&MSG_INPUTS[..2], retrieved)
+}
+
+// Two subscribers publish in alternating order in a multi branch channel, syncing their state before
+// each publish, with the fault applied to the 2nd message of Subscriber A. Returns how many messages
+// of each subscriber the author retrieves once every held back message has been delivered
+async fn alternate<T: Transport>(mut transport: FaultyTransport<T>, fault: Fault) -> Result<(usize, usize)> {
+    let mut author = Author::new(SEED, ChannelType::MultiBranch, transport.clone());
+    let announcement_link = author.send_announce().await?;
+    let mut subscribers = vec![
+        Subscriber::new("SubscriberA", transport.clone()),
+        Subscriber::new("SubscriberB", transport.clone()),
+    ];
+    let mut publishers = Vec::new();
+    for subscriber in &mut subscribers {
+        subscriber.receive_announcement(&announcement_link).await?;
+        let subscribe_link = subscriber.send_subscribe(&announcement_link).await?;
+        author.receive_subscribe(&subscribe_link).await?;
+        publishers.push(PublicKey::from_bytes(subscriber.get_public_key().as_bytes())?);
+    }
+    let (mut prev_msg_link, _seq_link) = author.send_keyload_for_everyone(&announcement_link).await?;
+
+    for (i, input) in MSG_INPUTS.iter().enumerate() {
+        for (sender, subscriber) in subscribers.iter_mut().enumerate() {
+            if i == 1 && sender == 0 {
+                transport.inject_next(Some(fault));
+            }
+            subscriber.sync_state().await;
+            if let Ok((msg_link, _seq_link)) = subscriber
+                .send_signed_packet(&prev_msg_link, &Bytes::default(), &Bytes(input.as_bytes().to_vec()))
+                .await
+            {
+                prev_msg_link = msg_link;
+            }
+        }
+    }
+
+    transport.flush().await?;
+    let groups = group_by_publisher(author.fetch_all_next_msgs().await, &publishers);
+    Ok((groups.get(&publishers[0]).len(), groups.get(&publishers[1]).len()))
+}
+
+#[tokio::test]
+async fn faults_only_hold_up_the_sequence_of_their_publisher() -> Result<()> {
+    for fault in &[Fault::Drop, Fault::Duplicate] {
+        let (from_a, from_b) = alternate(FaultyTransport::scripted(transports::offline()), *fault).await?;
+        assert!(from_a < MSG_INPUTS.len(), "{:?}", fault);
+        assert_eq!(from_b, MSG_INPUTS.len(), "{:?}", fault);
+    }
+    for fault in &[Fault::Delay(3), Fault::Reorder] {
+        let counts = alternate(FaultyTransport::scripted(transports::offline()), *fault).await?;
+        assert_eq!(counts, (MSG_INPUTS.len(), MSG_INPUTS.len()), "{:?}", fault);
+    }
+    Ok(())
+}
+
+#[test]
+fn rejects_invalid_probabilities() {
+    let config = FaultConfig {
+        drop: 1.5,
+        ..FaultConfig::default()
+    };
+    assert!(FaultyTransport::new(transports::offline(), config).is_err());
+    assert!(FaultyTransport::new(transports::offline(), FaultConfig::default()).is_ok());
+}
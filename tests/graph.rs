@@ -0,0 +1,68 @@
+use anyhow::Result;
+use iota_streams::app_channels::api::{
+    psk_from_seed, pskid_from_psk,
+    tangle::{Address, Author, Bytes, ChannelType},
+};
+use streams_examples::{
+    examples::SeedProvider,
+    graph::{self, ChannelGraph, EdgeKind, GraphWalker, NodeKind},
+    transports,
+};
+
+fn linked(graph: &ChannelGraph, from: &Address, to: &Address, kind: EdgeKind) -> bool {
+    let (from, to) = (graph.find(from), graph.find(to));
+    graph
+        .edges
+        .iter()
+        .any(|edge| Some(edge.from) == from && Some(edge.to) == to && edge.kind == kind)
+}
+
+#[tokio::test]
+async fn reconstructs_branches_and_sequencing() -> Result<()> {
+    let transport = transports::offline();
+    let mut seeds = SeedProvider::deterministic(14);
+    let mut author = Author::new(&seeds.author_seed(), ChannelType::MultiBranch, transport.clone());
+    let announcement_link = author.send_announce().await?;
+
+    let key = seeds.psk_key();
+    let pskid = pskid_from_psk(&psk_from_seed(&key));
+    author.store_psk(pskid, psk_from_seed(&key))?;
+
+    let (keyload_link, keyload_seq) = author.send_keyload(&announcement_link, &vec![pskid.into()]).await?;
+    let (private_link, _seq_link) = author
+        .send_signed_packet(&keyload_link, &Bytes::default(), &Bytes(b"private".to_vec()))
+        .await?;
+    let (public_link, _seq_link) = author
+        .send_signed_packet(&announcement_link, &Bytes::default(), &Bytes(b"public".to_vec()))
+        .await?;
+
+    // Without the key, the private packet cannot be unwrapped
+    let outsider = graph::walk(transport.clone(), &announcement_link).await?;
+    assert_eq!(outsider.node(&announcement_link).map(|n| n.kind), Some(NodeKind::Announce));
+    assert_eq!(outsider.node(&keyload_link).map(|n| n.kind), Some(NodeKind::Keyload));
+    assert_eq!(outsider.node(&private_link).map(|n| n.kind), Some(NodeKind::Unreadable));
+    assert_eq!(outsider.node(&public_link).map(|n| n.kind), Some(NodeKind::SignedPacket));
+    assert!(linked(&outsider, &announcement_link, &keyload_link, EdgeKind::Linked));
+    assert!(linked(&outsider, &keyload_link, &private_link, EdgeKind::Linked));
+    assert!(linked(&outsider, &announcement_link, &public_link, EdgeKind::Linked));
+    if let Some(seq_link) = keyload_seq {
+        assert_eq!(outsider.node(&seq_link).map(|n| n.kind), Some(NodeKind::Sequence));
+        assert!(linked(&outsider, &seq_link, &keyload_link, EdgeKind::Sequence));
+    }
+
+    let mut walker = GraphWalker::new(transport);
+    walker.reader().store_psk(pskid, psk_from_seed(&key))?;
+    walker.publisher("Author", author.get_public_key());
+    let reader = walker.walk(&announcement_link).await?;
+    let private = reader.node(&private_link).expect("private packet in graph");
+    assert_eq!(private.kind, NodeKind::SignedPacket);
+    assert_eq!(private.publisher.as_deref(), Some("Author"));
+
+    let dot = reader.to_dot();
+    assert!(dot.starts_with("digraph channel {"));
+    assert_eq!(dot.matches(" -> ").count(), reader.edges.len());
+    let mermaid = reader.to_mermaid();
+    assert!(mermaid.starts_with("graph TD"));
+    assert_eq!(mermaid.lines().count(), 1 + reader.nodes.len() + reader.edges.len());
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use iota_streams::app_channels::api::tangle::{
+    Author, Bytes, ChannelType, MessageContent, PublicKey, Subscriber,
+};
+use streams_examples::{
+    examples::{group_by_publisher, verify_messages, SeedProvider},
+    transports,
+};
+
+#[tokio::test]
+async fn groups_by_expected_publisher() -> Result<()> {
+    let transport = transports::offline();
+    let mut author = Author::new(
+        &SeedProvider::deterministic(9).author_seed(),
+        ChannelType::MultiBranch,
+        transport.clone(),
+    );
+    let announcement_link = author.send_announce().await?;
+
+    let mut subscribers = Vec::new();
+    for name in &["SubscriberA", "SubscriberB", "SubscriberC"] {
+        let mut subscriber = Subscriber::new(name, transport.clone());
+        subscriber.receive_announcement(&announcement_link).await?;
+        author.receive_subscribe(&subscriber.send_subscribe(&announcement_link).await?).await?;
+        subscribers.push(subscriber);
+    }
+    let pks = subscribers
+        .iter()
+        .map(|s| PublicKey::from_bytes(s.get_public_key().as_bytes()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (mut prev_msg_link, _seq_link) = author.send_keyload_for_everyone(&announcement_link).await?;
+    for (subscriber, input) in subscribers.iter_mut().zip(&["a", "b", "c"]) {
+        subscriber.sync_state().await;
+        let (msg_link, _seq_link) = subscriber
+            .send_signed_packet(&prev_msg_link, &Bytes::default(), &Bytes(input.as_bytes().to_vec()))
+            .await?;
+        prev_msg_link = msg_link;
+    }
+    subscribers[0].sync_state().await;
+    subscribers[0]
+        .send_tagged_packet(&prev_msg_link, &Bytes::default(), &Bytes(b"tagged".to_vec()))
+        .await?;
+
+    // Subscriber C publishes, but is not an expected publisher
+    let mut groups = group_by_publisher(author.fetch_all_next_msgs().await, &pks[..2]);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups.get(&pks[1]).len(), 1);
+    verify_messages(&["a"], groups.take(&pks[0]))?;
+    assert!(groups.take(&pks[0]).is_empty());
+    verify_messages(&["c"], groups.unknown)?;
+    assert!(groups
+        .unsigned
+        .iter()
+        .any(|msg| matches!(msg.body, MessageContent::TaggedPacket { .. })));
+    assert!(groups
+        .unsigned
+        .iter()
+        .all(|msg| !matches!(msg.body, MessageContent::SignedPacket { .. })));
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+};
+use streams_examples::{
+    examples::{self, SeedProvider},
+    local_node::{LocalNode, Store, MAX_BODY_SIZE},
+    transports,
+};
+
+// The tangle client expects to be driven by a multi threaded runtime, as it is in `main.rs`
+#[tokio::test(flavor = "multi_thread")]
+async fn example_runs_against_local_node() -> Result<()> {
+    let url = LocalNode::bind(([127, 0, 0, 1], 0), Store::in_memory())?.spawn()?;
+    examples::single_branch_private::example(transports::node(&url), &mut SeedProvider::random()).await?;
+    Ok(())
+}
+
+#[test]
+fn stored_messages_survive_a_restart() -> Result<()> {
+    let path = std::env::temp_dir().join(format!("streams-examples-node-{}.jsonl", std::process::id()));
+    let message = json!({ "payload": { "type": 2, "index": "ABCD", "data": "00" } });
+
+    let id = Store::open(&path)?.submit(message.clone())?;
+
+    let store = Store::open(&path)?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(store.len(), 1);
+    assert_eq!(store.find_by_index("abcd"), vec![id.clone()]);
+    assert_eq!(store.get(&id), Some(&message));
+    Ok(())
+}
+
+// Send a raw request and return the status line and JSON body of the response
+fn request(url: &str, head: &str, body: &[u8]) -> Result<(String, Value)> {
+    let mut stream = TcpStream::connect(url.trim_start_matches("http://"))?;
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body)?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let status = head.lines().next().unwrap_or_default().to_string();
+    Ok((status, serde_json::from_str(body)?))
+}
+
+#[test]
+fn answers_bad_requests_with_errors() -> Result<()> {
+    let url = LocalNode::bind(([127, 0, 0, 1], 0), Store::in_memory())?.spawn()?;
+
+    let body = b"not json";
+    let head = format!("POST /api/v1/messages HTTP/1.1\r\nContent-Length: {}\r\n\r\n", body.len());
+    let (status, response) = request(&url, &head, body)?;
+    assert!(status.contains("400"), "{}", status);
+    assert_eq!(response["error"]["code"], "400");
+
+    // The announced length alone is refused, before any of the body is read
+    let head = format!("POST /api/v1/messages HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY_SIZE + 1);
+    let (status, response) = request(&url, &head, b"")?;
+    assert!(status.contains("413"), "{}", status);
+    assert_eq!(response["error"]["code"], "413");
+
+    let (status, _) = request(&url, "GET /api/v1/info HTTP/1.1\r\nContent-Length: nope\r\n\r\n", b"")?;
+    assert!(status.contains("400"), "{}", status);
+    Ok(())
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use anyhow::Result;
+use common::Channel;
+use iota_streams::app_channels::api::tangle::{Bytes, ChannelType};
+use streams_examples::{
+    payload::{
+        metadata::{self, IndexEntry, Published},
+        Codec, Filter, Format, Metadata, MetadataReader,
+    },
+    transports,
+};
+
+#[tokio::test]
+async fn lists_and_filters_without_keys() -> Result<()> {
+    let transport = transports::offline();
+    let mut channel = Channel::new(transport.clone(), 19, ChannelType::MultiBranch).await?;
+    let sub_pk = channel.subscribe().await?;
+    let Channel {
+        mut author,
+        subscriber,
+        announcement_link,
+        ..
+    } = channel;
+    let (keyload_link, _seq_link) = author.send_keyload(&announcement_link, &vec![sub_pk.into()]).await?;
+
+    // Data goes to the private branch, the index to a public chain attached to the announcement
+    let records = vec![
+        Metadata::new("temperature", 1_000, "sensor-1"),
+        Metadata::new("humidity", 2_000, "sensor-1"),
+        Metadata::new("temperature", 3_000, "sensor-2"),
+    ];
+    let mut data_link = keyload_link;
+    let mut index_link = announcement_link.clone();
+    let mut published: Vec<Published> = Vec::new();
+    for (metadata, value) in records.iter().zip(&["21.5", "40", "19.0"]) {
+        let record = metadata::publish(&mut author, &data_link, &index_link, metadata, &Bytes(value.as_bytes().to_vec())).await?;
+        data_link = record.data_link.clone();
+        index_link = record.index_link.clone();
+        published.push(record);
+    }
+
+    let mut outsider = MetadataReader::new(transport, &announcement_link).await?;
+    assert_eq!(outsider.refresh().await, records.len());
+    let listed: Vec<&Metadata> = outsider.entries().iter().map(|entry| &entry.metadata).collect();
+    assert_eq!(listed, records.iter().collect::<Vec<_>>());
+    assert!(outsider.entries().iter().all(|entry| entry.index_link.is_some() && entry.publisher.is_some()));
+
+    let temperatures = outsider.filter(&Filter::default().kind("temperature"));
+    assert_eq!(temperatures.len(), 2);
+    let recent = outsider.filter(&Filter::default().device("sensor-1").between(1_500, 5_000));
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].data_link, published[1].data_link.to_string());
+    assert_eq!(outsider.refresh().await, 0);
+
+    // A subscriber with access lists every record once, from both the packet and its index entry
+    let mut reader = MetadataReader::with_subscriber(subscriber);
+    assert_eq!(reader.refresh().await, records.len());
+    assert!(reader.entries().iter().all(|entry| entry.index_link.is_some() && entry.from_packet));
+    Ok(())
+}
+
+#[tokio::test]
+async fn data_packets_override_forged_index_entries() -> Result<()> {
+    let transport = transports::offline();
+    let mut channel = Channel::new(transport.clone(), 19, ChannelType::MultiBranch).await?;
+    let sub_pk = channel.subscribe().await?;
+    let mut intruder = channel.reader("Intruder").await?;
+    let subscribe_link = intruder.send_subscribe(&channel.announcement_link).await?;
+    channel.author.receive_subscribe(&subscribe_link).await?;
+    let Channel {
+        mut author,
+        subscriber,
+        announcement_link,
+        ..
+    } = channel;
+
+    // The data branch is private to the subscriber, which shares another branch with the intruder
+    let (data_branch, _seq_link) = author.send_keyload(&announcement_link, &vec![sub_pk.into()]).await?;
+    let (shared_branch, _seq_link) = author.send_keyload_for_everyone(&announcement_link).await?;
+    let metadata = Metadata::new("temperature", 1_000, "sensor-1");
+    let record = metadata::publish(&mut author, &data_branch, &announcement_link, &metadata, &Bytes(b"21.5".to_vec())).await?;
+
+    // The intruder indexes the data packet as its own, with other metadata
+    let forged = IndexEntry {
+        metadata: Metadata::new("humidity", 2_000, "sensor-9"),
+        data_link: record.data_link.to_string(),
+    };
+    intruder.fetch_all_next_msgs().await;
+    let (forged_link, _seq_link) = intruder
+        .send_signed_packet(&shared_branch, &Codec::new(Format::Json).encode(&forged)?, &Bytes::default())
+        .await?;
+
+    // Whichever arrives first, the subscriber lists the record as the packet describes it
+    let mut reader = MetadataReader::with_subscriber(subscriber);
+    assert_eq!(reader.refresh().await, 1);
+    let entry = &reader.entries()[0];
+    assert_eq!(entry.metadata, metadata);
+    assert!(entry.from_packet);
+    assert_eq!(
+        entry.publisher.as_ref().map(|pk| pk.as_bytes().to_vec()),
+        Some(author.get_public_key().as_bytes().to_vec())
+    );
+    assert_ne!(entry.index_link, Some(forged_link.to_string()));
+    assert!(entry.index_link.iter().all(|link| *link == record.index_link.to_string()));
+    Ok(())
+}
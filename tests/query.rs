@@ -0,0 +1,97 @@
+mod common;
+
+use anyhow::Result;
+use common::{Channel, Packet};
+use iota_streams::app_channels::api::tangle::{Bytes, ChannelType, MessageContent, PublicKey, UnwrappedMessage};
+use serde::{Deserialize, Serialize};
+use streams_examples::{
+    examples::verify_messages,
+    payload::{Codec, Format, Record},
+    query::{Kind, Query},
+    transports,
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Level {
+    percent: u8,
+}
+
+impl Record for Level {
+    const SCHEMA: &'static str = "level";
+    const VERSION: u16 = 1;
+}
+
+fn texts(msgs: &[&UnwrappedMessage]) -> Vec<String> {
+    msgs.iter()
+        .filter_map(|msg| match &msg.body {
+            MessageContent::SignedPacket { masked_payload, .. } | MessageContent::TaggedPacket { masked_payload, .. } => {
+                Some(String::from_utf8_lossy(&masked_payload.0).into_owned())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn filters_with_composed_queries() -> Result<()> {
+    let mut channel = Channel::new(transports::offline(), 21, ChannelType::MultiBranch).await?;
+    let author_pk = PublicKey::from_bytes(channel.author.get_public_key().as_bytes())?;
+    let mut subscriber_b = channel.reader("SubscriberB").await?;
+    let sub_a_pk = channel.subscribe().await?;
+
+    // A public chain of signed and tagged packets, then a branch for Subscriber A with typed records
+    let public_links = channel
+        .send_chain(&[
+            Packet::signed(b"", b"a"),
+            Packet::tagged(b"", b"b"),
+            Packet::signed(b"", b"c"),
+            Packet::tagged(b"", b"d"),
+        ])
+        .await?;
+    let Channel {
+        mut author,
+        subscriber: mut subscriber_a,
+        announcement_link,
+        ..
+    } = channel;
+
+    let (keyload_link, _seq_link) = author.send_keyload(&announcement_link, &vec![sub_a_pk.into()]).await?;
+    let codec = Codec::new(Format::Cbor);
+    let mut prev_msg_link = keyload_link.clone();
+    for percent in &[10, 55, 90] {
+        let (msg_link, _seq_link) = author
+            .send_signed_packet(&prev_msg_link, &Bytes::default(), &codec.encode(&Level { percent: *percent })?)
+            .await?;
+        prev_msg_link = msg_link;
+    }
+
+    let retrieved = subscriber_a.fetch_all_next_msgs().await;
+    assert_eq!(Query::kind(Kind::Keyload).count(&retrieved), 1);
+    assert_eq!(Query::kind(Kind::TaggedPacket).count(&retrieved), 2);
+    assert_eq!(Query::in_branch(&keyload_link).count(&retrieved), 3);
+
+    let public_signed = Query::before_keyloads().and(Query::publisher(&author_pk));
+    assert_eq!(
+        public_signed.run(&retrieved).iter().map(|msg| &msg.link).collect::<Vec<_>>(),
+        vec![&public_links[0], &public_links[2]]
+    );
+
+    let range = Query::between(&public_links[1], &public_links[2]);
+    assert_eq!(texts(&range.run(&retrieved)), vec!["b", "c"]);
+    let packets = Query::packets().and(Query::before(&public_links[2]));
+    assert_eq!(texts(&packets.run(&retrieved)), vec!["a", "b"]);
+    let not_b = Query::before_keyloads().and(!Query::masked_text("b"));
+    assert_eq!(texts(&not_b.run(&retrieved)), vec!["a", "c", "d"]);
+
+    let high = Query::in_branch(&keyload_link).and(Query::masked_record(|level: &Level| level.percent > 50));
+    assert_eq!(high.count(&retrieved), 2);
+    let (public, rest) = Query::before_keyloads().partition(retrieved);
+    verify_messages(&["a", "b", "c", "d"], public)?;
+    assert_eq!(rest.len(), 4);
+
+    // Subscriber B cannot read the branch, and nothing in it decodes
+    let retrieved = subscriber_b.fetch_all_next_msgs().await;
+    assert_eq!(Query::kind(Kind::Unreadable).count(&retrieved), 3);
+    assert_eq!(Query::masked_record(|_: &Level| true).count(&retrieved), 0);
+    Ok(())
+}
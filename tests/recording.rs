@@ -0,0 +1,102 @@
+use anyhow::Result;
+use iota_streams::app_channels::api::tangle::{
+    Address, Author, Bytes, ChannelType, Subscriber, Transport, UnwrappedMessage,
+};
+use std::path::Path;
+use streams_examples::{
+    examples::{self, verify_messages, SeedProvider},
+    transports::{self, RecordingTransport, ReplayTransport},
+};
+
+const SEED: &str = "RECORDINGSEED99999999999999999999999999999999999999999999999999999999999999999999";
+const MSG_INPUTS: [&str; 3] = ["Recorded", "And", "Replayed"];
+
+async fn publish<T: Transport>(transport: T, seed: &str) -> Result<Address> {
+    let mut author = Author::new(seed, ChannelType::SingleBranch, transport);
+    let announcement_link = author.send_announce().await?;
+
+    let mut prev_msg_link = announcement_link.clone();
+    for input in &MSG_INPUTS {
+        let (msg_link, _seq_link) = author
+            .send_signed_packet(&prev_msg_link, &Bytes::default(), &Bytes(input.as_bytes().to_vec()))
+            .await?;
+        prev_msg_link = msg_link;
+    }
+    Ok(announcement_link)
+}
+
+async fn read<T: Transport>(transport: T, announcement_link: &Address) -> Result<Vec<UnwrappedMessage>> {
+    let mut subscriber = Subscriber::new("SubscriberA", transport);
+    subscriber.receive_announcement(announcement_link).await?;
+    Ok(subscriber.fetch_all_next_msgs().await)
+}
+
+fn fixture_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("streams-examples-{}-{}.json", name, std::process::id()))
+}
+
+#[tokio::test]
+async fn replay_serves_recorded_messages() -> Result<()> {
+    let recorder = RecordingTransport::new(transports::offline());
+    let announcement_link = publish(recorder.clone(), SEED).await?;
+    read(recorder.clone(), &announcement_link).await?;
+
+    let path = fixture_path("replay");
+    recorder.save(&path)?;
+    let replay = ReplayTransport::load(&path)?;
+    std::fs::remove_file(&path)?;
+
+    let retrieved = read(replay, &announcement_link).await?;
+    assert_eq!(retrieved.len(), MSG_INPUTS.len());
+    verify_messages(&MSG_INPUTS, retrieved)
+}
+
+#[tokio::test]
+async fn replay_accepts_identical_sends() -> Result<()> {
+    let recorder = RecordingTransport::new(transports::offline());
+    let announcement_link = publish(recorder.clone(), SEED).await?;
+
+    let replay = ReplayTransport::new(recorder.fixture());
+    assert_eq!(publish(replay, SEED).await?, announcement_link);
+    Ok(())
+}
+
+#[tokio::test]
+async fn replay_rejects_diverging_sends() -> Result<()> {
+    let recorder = RecordingTransport::new(transports::offline());
+    publish(recorder.clone(), SEED).await?;
+
+    let other_seed = SEED.replace("RECORDING", "DIVERGING");
+    let replay = ReplayTransport::new(recorder.fixture());
+    assert!(publish(replay, &other_seed).await.is_err());
+    Ok(())
+}
+
+// Checked in, so that a change of the message format of the Streams dependency fails the replay. Set
+// STREAMS_UPDATE_FIXTURES to record it again after a deliberate change
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/multi_branch_mixed_privacy.json");
+const FIXTURE_SEED: u64 = 4;
+
+fn fixture_seeds() -> SeedProvider {
+    SeedProvider::deterministic(FIXTURE_SEED).for_example("multi_branch_mixed_privacy")
+}
+
+#[tokio::test]
+async fn replays_checked_in_fixture() -> Result<()> {
+    if std::env::var_os("STREAMS_UPDATE_FIXTURES").is_some() {
+        let recorder = RecordingTransport::new(transports::offline());
+        examples::multi_branch_mixed_privacy::example(recorder.clone(), &mut fixture_seeds()).await?;
+        std::fs::create_dir_all(Path::new(FIXTURE).parent().expect("a fixtures directory"))?;
+        recorder.save(FIXTURE)?;
+    }
+
+    anyhow::ensure!(
+        Path::new(FIXTURE).exists(),
+        "Fixture {} is missing, record it with STREAMS_UPDATE_FIXTURES=1 cargo test --test recording",
+        FIXTURE
+    );
+    // The example sends exactly what was recorded and verifies what it retrieves from the fixture
+    let replay = ReplayTransport::load(FIXTURE)?;
+    examples::multi_branch_mixed_privacy::example(replay, &mut fixture_seeds()).await?;
+    Ok(())
+}
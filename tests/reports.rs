@@ -0,0 +1,39 @@
+use anyhow::Result;
+use streams_examples::{
+    examples::{self, SeedProvider},
+    runner::{self, Example},
+    transports,
+};
+
+#[tokio::test]
+async fn reports_links_and_retrievals() -> Result<()> {
+    let mut seeds = SeedProvider::deterministic(7);
+    let report = examples::single_branch_private::example(transports::offline(), &mut seeds).await?;
+
+    assert_eq!(report.channel_type.as_deref(), Some("SingleBranch"));
+    assert!(report.announcement.is_some());
+    assert_eq!(report.subscriptions.len(), 2);
+    assert_eq!(report.keyloads.len(), 1);
+    assert_eq!(report.messages.len(), 10);
+    assert!(report.retrieved.iter().all(|r| r.count == 11));
+    assert_eq!(
+        report.timings.iter().map(|t| t.phase.as_str()).collect::<Vec<_>>(),
+        vec!["setup", "publish", "fetch"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn run_results_serialize_to_json() {
+    let seeds = SeedProvider::deterministic(7);
+    let results = runner::run_all(&[Example::FetchPrev], transports::offline, &seeds, 1).await;
+    let json = runner::to_json(&results, Some(7));
+
+    assert_eq!(json["seed"], 7);
+    assert_eq!(json["passed"], 1);
+    let result = &json["results"][0];
+    assert_eq!(result["example"], "fetch_prev");
+    assert_eq!(result["passed"], true);
+    assert_eq!(result["report"]["messages"].as_array().map(Vec::len), Some(11));
+    assert_eq!(result["report"]["retrieved"][0]["count"], 10);
+}
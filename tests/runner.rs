@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+use streams_examples::{
+    examples::SeedProvider,
+    runner::{self, Example, RunDir, RunResult, Section},
+    transports,
+};
+
+#[test]
+fn empty_selection_runs_everything() {
+    let selected = runner::select(&[]).unwrap();
+    assert_eq!(selected, Example::ALL.to_vec());
+}
+
+#[test]
+fn select_by_section_and_name() {
+    let names = vec!["multi_publisher".to_string(), "fetch_prev".to_string()];
+    let selected = runner::select(&names).unwrap();
+    assert_eq!(
+        selected,
+        vec![Example::SinglePubPerBranch, Example::MultiPubPerBranch, Example::FetchPrev]
+    );
+    assert!(selected[..2].iter().all(|e| e.section() == Section::MultiPublisher));
+}
+
+#[test]
+fn duplicate_selections_run_once() {
+    let names = vec!["utility".to_string(), "state_recovery".to_string()];
+    let selected = runner::select(&names).unwrap();
+    assert_eq!(selected.iter().filter(|e| **e == Example::StateRecovery).count(), 1);
+}
+
+#[test]
+fn unknown_names_are_rejected() {
+    assert!(runner::select(&["not_an_example".to_string()]).is_err());
+}
+
+// What a run sent and retrieved, leaving out timings
+fn outcome(result: &RunResult) -> serde_json::Value {
+    let report = result.report.as_ref().expect("a report of the run");
+    serde_json::json!({
+        "announcement": report.announcement,
+        "messages": report.messages,
+        "retrieved": report.retrieved,
+    })
+}
+
+#[tokio::test]
+async fn concurrent_runs_do_not_share_state() {
+    let seeds = SeedProvider::deterministic(11);
+    let alone = runner::run_all(&Example::ALL, transports::offline, &seeds, 1).await;
+
+    // Every example twice, all at once on as many threads
+    let selected = [Example::ALL, Example::ALL].concat();
+    let results = runner::run_all(&selected, transports::offline, &seeds, selected.len()).await;
+
+    assert_eq!(results.iter().map(|r| r.example).collect::<Vec<_>>(), selected);
+    for result in &results {
+        assert!(result.passed(), "{}: {:?}", result.example.name(), result.error);
+    }
+
+    // Each run had a directory of its own, removed once it completed
+    let dirs: HashSet<_> = results.iter().chain(&alone).filter_map(|r| r.dir.clone()).collect();
+    assert_eq!(dirs.len(), results.len() + alone.len());
+    assert!(dirs.iter().all(|dir| !dir.exists()));
+
+    // and sent and retrieved exactly what it does when run alone, nothing another run sent
+    for (result, expected) in results.iter().zip(alone.iter().cycle()) {
+        assert_eq!(outcome(result), outcome(expected), "{}", result.example.name());
+    }
+}
+
+#[test]
+fn run_dirs_are_unique_and_removed() -> anyhow::Result<()> {
+    let first = RunDir::new("state_recovery")?;
+    let second = RunDir::new("state_recovery")?;
+    assert_ne!(first.path(), second.path());
+
+    let path = first.path().to_path_buf();
+    std::fs::write(path.join("author_state.bin"), b"state")?;
+    drop(first);
+    assert!(!path.exists());
+    Ok(())
+}
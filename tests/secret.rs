@@ -0,0 +1,53 @@
+mod common;
+
+use anyhow::Result;
+use common::Channel;
+use iota_streams::app_channels::api::tangle::{Author, ChannelType};
+use streams_examples::{
+    runner::RunDir,
+    state::{reencrypt, Password, PasswordSource},
+    transports,
+};
+
+#[test]
+fn loads_passwords_from_env_and_key_files() -> Result<()> {
+    // A variable of its own, tests run concurrently
+    std::env::set_var("STREAMS_SECRET_TEST_PASSWORD", "from env");
+    let source = PasswordSource::Env("STREAMS_SECRET_TEST_PASSWORD".to_string());
+    assert_eq!(source.load()?.expose(), "from env");
+    assert!(PasswordSource::Env("STREAMS_SECRET_TEST_UNSET".to_string()).load().is_err());
+
+    let dir = RunDir::new("secret")?;
+    let key_file = dir.path().join("state.key");
+    std::fs::write(&key_file, "from file\n")?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&key_file, std::fs::Permissions::from_mode(0o644))?;
+        assert!(PasswordSource::File(key_file.clone()).load().is_err());
+        std::fs::set_permissions(&key_file, std::fs::Permissions::from_mode(0o600))?;
+    }
+    let password = PasswordSource::File(key_file.clone()).load()?;
+    assert_eq!(password.expose(), "from file");
+    assert!(!format!("{:?}", password).contains("from file"));
+
+    std::fs::write(&key_file, "\n")?;
+    assert!(PasswordSource::File(key_file).load().is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn reencrypts_exported_states() -> Result<()> {
+    let transport = transports::offline();
+    let author = Channel::new(transport.clone(), 23, ChannelType::SingleBranch).await?.author;
+
+    let current = Password::new("current".to_string());
+    let new = Password::new("new".to_string());
+    let state = author.export(current.expose()).await?;
+    let state = reencrypt::<Author<_>>(&state, &current, &new, transport.clone()).await?;
+
+    assert!(Author::import(&state, current.expose(), transport.clone()).await.is_err());
+    let imported = Author::import(&state, new.expose(), transport).await?;
+    assert_eq!(imported.get_public_key().as_bytes(), author.get_public_key().as_bytes());
+    Ok(())
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use anyhow::Result;
+use common::Channel;
+use iota_streams::app_channels::api::tangle::{Bytes, ChannelType};
+use streams_examples::{
+    examples::{segments::take_segment, split_at_keyloads, verify_segment, Access},
+    transports,
+};
+
+#[tokio::test]
+async fn splits_at_keyloads_with_access() -> Result<()> {
+    let mut channel = Channel::new(transports::offline(), 13, ChannelType::MultiBranch).await?;
+    let mut subscriber_b = channel.reader("SubscriberB").await?;
+    let sub_a_pk = channel.subscribe().await?;
+    let Channel {
+        mut author,
+        subscriber: mut subscriber_a,
+        announcement_link,
+        ..
+    } = channel;
+
+    // A public message, a branch for Subscriber A only, and a keyload nothing is sent after
+    let (public_link, _seq_link) = author
+        .send_signed_packet(&announcement_link, &Bytes::default(), &Bytes(b"public".to_vec()))
+        .await?;
+    let (keyload_a_link, _seq_link) = author.send_keyload(&public_link, &vec![sub_a_pk.into()]).await?;
+    let (private_link, _seq_link) = author
+        .send_signed_packet(&keyload_a_link, &Bytes::default(), &Bytes(b"private".to_vec()))
+        .await?;
+    let (keyload_last_link, _seq_link) = author.send_keyload_for_everyone(&private_link).await?;
+
+    let mut segments = split_at_keyloads(subscriber_b.fetch_all_next_msgs().await);
+    assert_eq!(segments.len(), 3);
+    assert_eq!(segments[0].keyload, None);
+    assert_eq!(segments[0].access, Access::Granted);
+    verify_segment(&mut segments, &keyload_a_link, Access::Denied, &[])?;
+    assert_eq!(take_segment(&mut segments, &keyload_last_link)?.access, Access::Unknown);
+    assert!(take_segment(&mut segments, &keyload_a_link).is_err());
+
+    let mut segments = split_at_keyloads(subscriber_a.fetch_all_next_msgs().await);
+    verify_segment(&mut segments, &keyload_a_link, Access::Granted, &["private"])?;
+    assert!(verify_segment(&mut segments, &keyload_last_link, Access::Granted, &[]).is_err());
+    Ok(())
+}
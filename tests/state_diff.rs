@@ -0,0 +1,101 @@
+use anyhow::Result;
+use iota_streams::app_channels::api::{
+    psk_from_seed, pskid_from_psk,
+    tangle::{Author, Bytes, ChannelType},
+};
+use streams_examples::{
+    examples::SeedProvider,
+    state::{diff, inspect, Ahead, Password, PublisherState, Role, StateSummary},
+    transports,
+};
+
+#[tokio::test]
+async fn detects_snapshots_ahead_and_diverged() -> Result<()> {
+    let transport = transports::offline();
+    let mut seeds = SeedProvider::deterministic(25);
+    let password = Password::new("diff".to_string());
+    let mut author = Author::new(&seeds.author_seed(), ChannelType::SingleBranch, transport.clone());
+    let announcement_link = author.send_announce().await?;
+    let snapshot = author.export(password.expose()).await?;
+    let first = inspect(Role::Author, &snapshot, &password, transport.clone()).await?;
+
+    author
+        .send_signed_packet(&announcement_link, &Bytes::default(), &Bytes(b"ahead".to_vec()))
+        .await?;
+    let second = inspect(Role::Author, &author.export(password.expose()).await?, &password, transport.clone()).await?;
+
+    assert!(diff(&first, &first)?.is_empty());
+    let forward = diff(&first, &second)?;
+    assert_eq!(forward.ahead, Ahead::Second);
+    assert_eq!(forward.publishers.len(), 1);
+    assert_eq!(forward.publishers[0].public_key, first.public_key);
+    assert_eq!(diff(&second, &first)?.ahead, Ahead::First);
+
+    // Another process restores the first snapshot, publishes from it and stores a PSK the first
+    // process never saw
+    let mut restored = Author::import(&snapshot, password.expose(), transport.clone()).await?;
+    restored
+        .send_signed_packet(&announcement_link, &Bytes::default(), &Bytes(b"diverged".to_vec()))
+        .await?;
+    let key = seeds.psk_key();
+    let pskid = pskid_from_psk(&psk_from_seed(&key));
+    restored.store_psk(pskid, psk_from_seed(&key))?;
+    let other = inspect(Role::Author, &restored.export(password.expose()).await?, &password, transport).await?;
+
+    let diverged = diff(&second, &other)?;
+    assert_eq!(diverged.ahead, Ahead::Diverged);
+    assert_eq!(diverged.psk_ids_added, vec![hex::encode(pskid)]);
+    assert!(diverged.to_string().contains("diverged"));
+    Ok(())
+}
+
+fn summary(subscribers: &[&str], seq_no: u32) -> StateSummary {
+    StateSummary {
+        role: Role::Author,
+        public_key: "aa".repeat(32),
+        announcement_link: Some("announcement".to_string()),
+        channel_type: Some("SingleBranch".to_string()),
+        author_public_key: Some("aa".repeat(32)),
+        publishers: vec![PublisherState {
+            public_key: "aa".repeat(32),
+            link: format!("link{}", seq_no),
+            branch_no: 0,
+            seq_no,
+        }],
+        psk_ids: Vec::new(),
+        subscribers: subscribers.iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+#[test]
+fn reports_subscriber_changes_and_rejects_other_identities() -> Result<()> {
+    let changes = diff(&summary(&["sub_a", "sub_b"], 3), &summary(&["sub_b", "sub_c"], 3))?;
+    assert_eq!(changes.subscribers_added, vec!["sub_c".to_string()]);
+    assert_eq!(changes.subscribers_removed, vec!["sub_a".to_string()]);
+    assert!(changes.publishers.is_empty());
+    // Subscriber changes alone do not put either snapshot ahead
+    assert_eq!(changes.ahead, Ahead::Neither);
+    assert!(!changes.is_empty());
+
+    let removed = diff(&summary(&["sub_a"], 3), &summary(&[], 4))?;
+    assert_eq!(removed.subscribers_removed, vec!["sub_a".to_string()]);
+    assert_eq!(removed.ahead, Ahead::Second);
+
+    let mut other = summary(&[], 3);
+    other.public_key = "bb".repeat(32);
+    assert!(diff(&summary(&[], 3), &other).unwrap_err().to_string().contains("public keys"));
+    let mut other = summary(&[], 3);
+    other.role = Role::Subscriber;
+    assert!(diff(&summary(&[], 3), &other).unwrap_err().to_string().contains("roles"));
+    let mut other = summary(&[], 3);
+    other.announcement_link = Some("elsewhere".to_string());
+    assert!(diff(&summary(&[], 3), &other).unwrap_err().to_string().contains("announcement links"));
+
+    // Identities are checked before either snapshot knows the channel
+    let (mut unannounced, mut other) = (summary(&[], 3), summary(&[], 3));
+    unannounced.announcement_link = None;
+    other.announcement_link = None;
+    other.public_key = "bb".repeat(32);
+    assert!(diff(&unannounced, &other).is_err());
+    Ok(())
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use anyhow::Result;
+use common::Channel;
+use iota_streams::app_channels::api::{psk_from_seed, pskid_from_psk, tangle::ChannelType};
+use streams_examples::{
+    examples::SeedProvider,
+    state::{inspect, Password, Role},
+    transports,
+};
+
+#[tokio::test]
+async fn summarises_author_and_subscriber_states() -> Result<()> {
+    let transport = transports::offline();
+    let mut channel = Channel::new(transport.clone(), 24, ChannelType::MultiBranch).await?;
+    channel.subscribe().await?;
+    let Channel {
+        mut author,
+        subscriber,
+        announcement_link,
+        ..
+    } = channel;
+
+    let key = SeedProvider::deterministic(24).psk_key();
+    let pskid = pskid_from_psk(&psk_from_seed(&key));
+    author.store_psk(pskid, psk_from_seed(&key))?;
+
+    let password = Password::new("inspect".to_string());
+    let author_pk = hex::encode(author.get_public_key().as_bytes());
+    let subscriber_pk = hex::encode(subscriber.get_public_key().as_bytes());
+
+    let state = author.export(password.expose()).await?;
+    let summary = inspect(Role::Author, &state, &password, transport.clone()).await?;
+    assert_eq!(summary.role, Role::Author);
+    assert_eq!(summary.public_key, author_pk);
+    assert_eq!(summary.announcement_link, Some(announcement_link.to_string()));
+    assert_eq!(summary.channel_type.as_deref(), Some("MultiBranch"));
+    assert_eq!(summary.psk_ids, vec![hex::encode(pskid)]);
+    assert_eq!(summary.subscribers, vec![subscriber_pk.clone()]);
+    assert!(summary.publishers.iter().any(|publisher| publisher.public_key == author_pk));
+
+    let json = serde_json::to_value(&summary)?;
+    assert_eq!(json["announcement_link"], announcement_link.to_string());
+    assert!(summary.to_string().contains(&subscriber_pk));
+
+    let state = subscriber.export(password.expose()).await?;
+    let summary = inspect(Role::Subscriber, &state, &password, transport.clone()).await?;
+    assert_eq!(summary.public_key, subscriber_pk);
+    assert_eq!(summary.author_public_key, Some(author_pk));
+    assert_eq!(summary.announcement_link, Some(announcement_link.to_string()));
+    assert!(summary.psk_ids.is_empty());
+    assert!(summary.subscribers.is_empty());
+
+    // A wrong password is reported rather than summarising garbage
+    assert!(inspect(Role::Subscriber, &state, &Password::new("wrong".to_string()), transport).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn reports_single_depth_channels() -> Result<()> {
+    let transport = transports::offline();
+    let channel = Channel::new(transport.clone(), 24, ChannelType::SingleDepth).await?;
+    let password = Password::new("inspect".to_string());
+
+    let state = channel.author.export(password.expose()).await?;
+    let summary = inspect(Role::Author, &state, &password, transport.clone()).await?;
+    assert_eq!(summary.channel_type.as_deref(), Some("SingleDepth"));
+
+    let state = channel.subscriber.export(password.expose()).await?;
+    let summary = inspect(Role::Subscriber, &state, &password, transport).await?;
+    assert_eq!(summary.channel_type.as_deref(), Some("SingleDepth"));
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use iota_streams::app_channels::api::tangle::{Author, Bytes, ChannelType, Subscriber};
+use streams_examples::{
+    examples::{verify_messages, SeedProvider},
+    publisher::Publisher,
+    runner::RunDir,
+    state::{Password, PersistentUser, StoreConfig},
+    transports,
+};
+
+#[tokio::test]
+async fn snapshots_rotate_and_restore() -> Result<()> {
+    let dir = RunDir::new("state_store")?;
+    let transport = transports::offline();
+    let seed = SeedProvider::deterministic(22).author_seed();
+    let password = Password::new("Password".to_string());
+    let config = StoreConfig { every: 1, keep: 2 };
+
+    let mut author = PersistentUser::restore_or(dir.path(), "author", &password, transport.clone(), config, |t| {
+        Author::new(&seed, ChannelType::SingleBranch, t)
+    })
+    .await?;
+    assert!(author.store().snapshots()?.is_empty());
+
+    let announcement_link = author.send_announce().await?;
+    let mut prev_msg_link = announcement_link.clone();
+    for input in &["Persisted", "After", "Every"] {
+        let (msg_link, _seq_link) = author
+            .send_signed_packet(&prev_msg_link, &Bytes::default(), &Bytes(input.as_bytes().to_vec()))
+            .await?;
+        prev_msg_link = msg_link;
+    }
+    assert_eq!(author.store().snapshots()?.len(), 2);
+    drop(author);
+
+    // The restored author picks up where the snapshot left off
+    let mut author: PersistentUser<Author<_>> =
+        PersistentUser::restore(dir.path(), "author", &password, transport.clone(), config)
+            .await?
+            .expect("a snapshot to restore");
+    author
+        .send_signed_packet(&prev_msg_link, &Bytes::default(), &Bytes(b"Send".to_vec()))
+        .await?;
+
+    let mut subscriber = Subscriber::new("SubscriberA", transport);
+    subscriber.receive_announcement(&announcement_link).await?;
+    verify_messages(&["Persisted", "After", "Every", "Send"], subscriber.fetch_all_next_msgs().await)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn skips_corrupt_snapshots() -> Result<()> {
+    let dir = RunDir::new("state_store")?;
+    let transport = transports::offline();
+    let seed = SeedProvider::deterministic(22).author_seed();
+    let password = Password::new("Password".to_string());
+    let config = StoreConfig { every: 2, keep: 3 };
+
+    let mut author = PersistentUser::new(
+        Author::new(&seed, ChannelType::SingleBranch, transport.clone()),
+        dir.path(),
+        "author",
+        &password,
+        config,
+    )?;
+    let announcement_link = author.send_announce().await?;
+    assert!(author.store().snapshots()?.is_empty());
+    let (msg_link, _seq_link) = author
+        .send_signed_packet(&announcement_link, &Bytes::default(), &Bytes(b"first".to_vec()))
+        .await?;
+    assert_eq!(author.store().snapshots()?.len(), 1);
+    author.snapshot().await?;
+
+    let snapshots = author.store().snapshots()?;
+    assert_eq!(snapshots.len(), 2);
+    std::fs::write(&snapshots[0], b"not a state")?;
+    // Leftovers of an interrupted save are cleared when the store is opened
+    std::fs::write(snapshots[0].with_extension("state.tmp"), b"partial")?;
+    drop(author);
+
+    let mut author: PersistentUser<Author<_>> =
+        PersistentUser::restore(dir.path(), "author", &password, transport.clone(), config)
+            .await?
+            .expect("the older snapshot to restore");
+    assert!(!snapshots[0].with_extension("state.tmp").exists());
+    author
+        .send_signed_packet(&msg_link, &Bytes::default(), &Bytes(b"second".to_vec()))
+        .await?;
+
+    let mut subscriber = Subscriber::new("SubscriberA", transport);
+    subscriber.receive_announcement(&announcement_link).await?;
+    verify_messages(&["first", "second"], subscriber.fetch_all_next_msgs().await)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn wrong_passwords_leave_snapshots_alone() -> Result<()> {
+    let dir = RunDir::new("state_store")?;
+    let transport = transports::offline();
+    let seed = SeedProvider::deterministic(22).author_seed();
+    let password = Password::new("Password".to_string());
+    let config = StoreConfig { every: 1, keep: 1 };
+
+    let mut author = PersistentUser::new(
+        Author::new(&seed, ChannelType::SingleBranch, transport.clone()),
+        dir.path(),
+        "author",
+        &password,
+        config,
+    )?;
+    author.send_announce().await?;
+    let snapshots = author.store().snapshots()?;
+    let state = std::fs::read(&snapshots[0])?;
+    drop(author);
+
+    // Creating a new user instead would rotate the only snapshot away with its first save
+    let wrong = Password::new("Wrong".to_string());
+    let restored = PersistentUser::restore_or(dir.path(), "author", &wrong, transport.clone(), config, |t| {
+        Author::new(&seed, ChannelType::SingleBranch, t)
+    })
+    .await;
+    let error = restored.err().expect("a wrong password to fail the restore").to_string();
+    assert!(error.contains("password is wrong"), "{}", error);
+    assert_eq!(std::fs::read_dir(dir.path())?.count(), 1);
+    assert_eq!(std::fs::read(&snapshots[0])?, state);
+    assert!(PersistentUser::<Author<_>>::restore(dir.path(), "author", &password, transport, config)
+        .await?
+        .is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn keeps_links_when_a_snapshot_fails() -> Result<()> {
+    let dir = RunDir::new("state_store")?;
+    let transport = transports::offline();
+    let seed = SeedProvider::deterministic(22).author_seed();
+    let password = Password::new("Password".to_string());
+    let config = StoreConfig { every: 1, keep: 2 };
+
+    let mut author = PersistentUser::new(
+        Author::new(&seed, ChannelType::SingleBranch, transport.clone()),
+        dir.path(),
+        "author",
+        &password,
+        config,
+    )?;
+    let announcement_link = author.send_announce().await?;
+    assert!(author.snapshot_error().is_none());
+
+    // The send goes through while its snapshot cannot be written
+    std::fs::remove_dir_all(dir.path())?;
+    let (msg_link, _seq_link) = author
+        .send_signed_packet(&announcement_link, &Bytes::default(), &Bytes(b"unsaved".to_vec()))
+        .await?;
+    assert!(author.snapshot_error().is_some());
+
+    // and the next operation retries it
+    std::fs::create_dir_all(dir.path())?;
+    author
+        .send_signed_packet(&msg_link, &Bytes::default(), &Bytes(b"saved".to_vec()))
+        .await?;
+    assert!(author.snapshot_error().is_none());
+    assert_eq!(author.store().snapshots()?.len(), 1);
+
+    let mut subscriber = Subscriber::new("SubscriberA", transport);
+    subscriber.receive_announcement(&announcement_link).await?;
+    verify_messages(&["unsaved", "saved"], subscriber.fetch_all_next_msgs().await)?;
+    Ok(())
+}
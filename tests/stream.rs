@@ -0,0 +1,65 @@
+mod common;
+
+use anyhow::Result;
+use common::Channel;
+use futures::StreamExt;
+use iota_streams::app_channels::api::tangle::{Bytes, ChannelType};
+use std::time::Duration;
+use streams_examples::{
+    examples::verify_messages,
+    stream::{follow, FollowConfig},
+    transports,
+};
+
+fn config() -> FollowConfig {
+    FollowConfig {
+        interval: Duration::from_millis(5),
+        max_interval: Duration::from_millis(20),
+        backoff: 2.0,
+    }
+}
+
+#[tokio::test]
+async fn yields_messages_as_they_are_published() -> Result<()> {
+    let Channel {
+        mut author,
+        mut subscriber,
+        announcement_link,
+        ..
+    } = Channel::new(transports::offline(), 20, ChannelType::SingleBranch).await?;
+
+    let msg_inputs = vec!["These", "Messages", "Are", "Followed", "Live"];
+    let publish = async {
+        let mut prev_msg_link = announcement_link.clone();
+        for input in &msg_inputs {
+            // Leave the subscriber polling an empty channel in between messages
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            let (msg_link, _seq_link) = author
+                .send_signed_packet(&prev_msg_link, &Bytes::default(), &Bytes(input.as_bytes().to_vec()))
+                .await?;
+            prev_msg_link = msg_link;
+        }
+        Ok::<_, anyhow::Error>(())
+    };
+
+    let (messages, _handle) = follow(&mut subscriber, config());
+    let (published, retrieved) = futures::join!(publish, messages.take(msg_inputs.len()).collect::<Vec<_>>());
+    published?;
+    verify_messages(&msg_inputs, retrieved)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn ends_when_aborted() -> Result<()> {
+    let mut channel = Channel::new(transports::offline(), 20, ChannelType::SingleBranch).await?;
+
+    let (mut messages, handle) = follow(&mut channel.subscriber, config());
+    let cancel = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        handle.abort();
+    };
+    let (_, next) = futures::join!(cancel, messages.next());
+    assert!(next.is_none());
+    assert!(messages.is_aborted());
+    Ok(())
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use anyhow::Result;
+use common::{send_and_fetch, Packet};
+use streams_examples::examples::{
+    verify::{verify_with, Packets, Payload, Positioned, VerifyOptions},
+    verify_messages, verify_public,
+};
+
+fn messages(positioned: &[Positioned]) -> Vec<&str> {
+    positioned.iter().map(|p| p.message.as_str()).collect()
+}
+
+#[tokio::test]
+async fn reports_missing_extra_and_reordered_messages() -> Result<()> {
+    let retrieved = send_and_fetch(
+        5,
+        &[
+            Packet::signed(b"", b"a"),
+            Packet::signed(b"", b"b"),
+            Packet::signed(b"", b"c"),
+            Packet::signed(b"", b"d"),
+        ],
+    )
+    .await?;
+
+    let error = verify_with(&["a", "c", "b", "e"], &retrieved, &VerifyOptions::default()).unwrap_err();
+    assert_eq!(messages(&error.missing), vec!["e"]);
+    assert_eq!(messages(&error.extra), vec!["d"]);
+    assert_eq!(error.reordered.len(), 1);
+    assert!(error.to_string().contains("missing: sent msg 3 \"e\""));
+    Ok(())
+}
+
+#[tokio::test]
+async fn more_messages_than_sent_are_extra() -> Result<()> {
+    let retrieved = send_and_fetch(
+        5,
+        &[
+            Packet::signed(b"", b"a"),
+            Packet::signed(b"", b"b"),
+            Packet::signed(b"", b"c"),
+        ],
+    )
+    .await?;
+
+    let error = verify_with(&["a"], &retrieved, &VerifyOptions::default()).unwrap_err();
+    assert_eq!(messages(&error.extra), vec!["b", "c"]);
+    assert!(error.missing.is_empty());
+    assert!(verify_messages(&["a"], retrieved).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn non_utf8_payloads_are_reported() -> Result<()> {
+    let retrieved = send_and_fetch(5, &[Packet::signed(b"", b"a"), Packet::signed(b"", &[0xff, 0xfe])]).await?;
+
+    let error = verify_with(&["a"], &retrieved, &VerifyOptions::default()).unwrap_err();
+    assert_eq!(messages(&error.non_utf8), vec!["fffe"]);
+    assert!(error.extra.is_empty() && error.missing.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn checks_tagged_packets_and_public_payloads() -> Result<()> {
+    let retrieved = send_and_fetch(
+        5,
+        &[
+            Packet::tagged(b"public a", b"masked a"),
+            Packet::tagged(b"public b", b"masked b"),
+        ],
+    )
+    .await?;
+
+    assert!(verify_with(&["masked a", "masked b"], &retrieved, &VerifyOptions::default()).is_ok());
+    let public = VerifyOptions {
+        payload: Payload::Public,
+        packets: Packets::Tagged,
+        ..VerifyOptions::default()
+    };
+    assert!(verify_with(&["public a", "public b"], &retrieved, &public).is_ok());
+
+    // The default check leaves public payloads out, they are checked on their own
+    assert!(verify_messages(&["masked a", "masked b"], retrieved.clone()).is_ok());
+    assert!(verify_public(&["public a", "public b"], retrieved.clone()).is_ok());
+    assert!(verify_public(&["public a", "masked b"], retrieved.clone()).is_err());
+
+    let signed_only = VerifyOptions {
+        packets: Packets::Signed,
+        ..VerifyOptions::default()
+    };
+    let error = verify_with(&[], &retrieved, &signed_only).unwrap_err();
+    assert_eq!(messages(&error.extra), vec!["<tagged packet>", "<tagged packet>"]);
+    Ok(())
+}